@@ -0,0 +1,564 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::cwt_executor::CommonCwtExecutor;
+use crate::err::try_vec;
+use crate::sample::CwtSample;
+use crate::{
+    Coherence, CoherenceOptions, CrossWavelet, CwtExecutor, DenoiseOptions, GlobalSpectrum,
+    NoiseEstimate, ReconstructionFormula, Ridge, RidgeOptions, ScaleBand, ScaletError,
+    Significance, SignificanceOptions, TimeFrequencyMask,
+};
+use num_complex::Complex;
+use num_traits::AsPrimitive;
+
+/// Analysis of wavelet coefficients on top of a [`CwtExecutor`].
+///
+/// Every method works on the scales, wavelet and normalization of the executor,
+/// so results line up with the rows of [`execute`](CwtExecutor::execute).
+/// Every CWT executor created by [`Scalet`](crate::Scalet) implements it, and can be used
+/// wherever a [`CwtExecutor`] is expected.
+pub trait CwtAnalysis<T>: CwtExecutor<T> {
+    /// Returns the e-folding time of every scale, in samples.
+    ///
+    /// Following Torrence & Compo, this is the distance from an edge over which the wavelet
+    /// power of a discontinuity drops by `e⁻²`, `√2 * scale` for the Morlet wavelet.
+    /// Coefficients closer to the signal edges than the e-folding time of their scale
+    /// are dominated by edge effects.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if the time-domain wavelet cannot be evaluated.
+    fn e_folding_times(&self) -> Result<Vec<T>, ScaletError>;
+    /// Returns the cone of influence of a signal of `length` samples.
+    ///
+    /// Value `t` is the largest scale unaffected by edge effects at sample `t`: the distance
+    /// to the nearest signal edge divided by the e-folding time per unit scale. Scales above
+    /// it lie inside the cone of influence. Edges are the first and last input sample, where
+    /// padding joins the signal to artificial data. [`PaddingMode::Periodic`](crate::PaddingMode::Periodic)
+    /// continues the signal into itself instead, its only edge is where the internal FFT buffer
+    /// wraps, and the cone is infinite everywhere when the buffer holds a whole number of periods.
+    /// The result lines up with the columns of [`execute`](CwtExecutor::execute).
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `length` is zero or longer than the executor length,
+    /// or if the time-domain wavelet cannot be evaluated.
+    fn cone_of_influence(&self, length: usize) -> Result<Vec<T>, ScaletError>;
+    /// Returns a `[scale][time]` mask of a signal of `length` samples that is `true`
+    /// for coefficients inside the cone of influence, see
+    /// [`cone_of_influence`](Self::cone_of_influence).
+    fn cone_of_influence_mask(&self, length: usize) -> Result<Vec<Vec<bool>>, ScaletError>;
+    /// Tests the wavelet power of `input` against a white or red noise background,
+    /// following Torrence & Compo.
+    ///
+    /// The background is fitted to `input`, and its expected power is evaluated at every scale
+    /// with the filters of this executor, so it accounts for the wavelet, the normalization
+    /// and the scales exactly. Every coefficient is compared with the chi-square distribution
+    /// of two degrees of freedom.
+    ///
+    /// # Arguments
+    /// * `input` - The signal `coeffs` were computed from.
+    /// * `coeffs` - Output of [`execute`](CwtExecutor::execute) for `input`.
+    /// * `options` - Noise model and confidence level.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `input` is empty, too long or constant, if `coeffs` does not
+    /// have one row of `input.len()` values per scale, or if the options are out of range.
+    fn significance(
+        &self,
+        input: &[T],
+        coeffs: &[Vec<Complex<T>>],
+        options: SignificanceOptions,
+    ) -> Result<Significance<T>, ScaletError>;
+    /// Returns the significance level of the time-averaged (global) wavelet spectrum of `input`
+    /// at every scale.
+    ///
+    /// Averaging neighbouring coefficients increases the degrees of freedom less than the number
+    /// of samples, since the coefficients are correlated over the wavelet width. The degrees of
+    /// freedom are derived from the autocorrelation of the background coefficients at each scale.
+    /// Levels are in the normalization of the executor, as the power of
+    /// [`global_wavelet_spectrum`](Self::global_wavelet_spectrum).
+    ///
+    /// # Arguments
+    /// * `input` - The tested signal.
+    /// * `samples` - Number of averaged coefficients at each scale, e.g. the samples outside
+    ///   the cone of influence. `None` averages all `input.len()` samples.
+    /// * `options` - Noise model and confidence level.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `input` is empty, too long or constant, if `samples` does not
+    /// have one value per scale, or if the options are out of range.
+    fn global_significance(
+        &self,
+        input: &[T],
+        samples: Option<&[usize]>,
+        options: SignificanceOptions,
+    ) -> Result<Vec<T>, ScaletError>;
+    /// Collapses `coeffs` into the global wavelet spectrum, the time-averaged power of every scale.
+    ///
+    /// Power is kept in the normalization of the executor, selected by `l1_norm`. With L1
+    /// normalization it follows the squared amplitude of the components. L2 rows are scaled
+    /// by `1/√a`, so their power is the L1 power divided by the scale. It can be compared
+    /// directly with [`global_significance`](Self::global_significance).
+    ///
+    /// # Arguments
+    /// * `coeffs` - Output of [`execute`](CwtExecutor::execute).
+    /// * `exclude_coi` - Averages only coefficients outside the cone of influence, see
+    ///   [`cone_of_influence_mask`](Self::cone_of_influence_mask).
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `coeffs` does not have one row per scale of equal length,
+    /// from one sample up to the executor length.
+    fn global_wavelet_spectrum(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        exclude_coi: bool,
+    ) -> Result<GlobalSpectrum<T>, ScaletError>;
+    /// Collapses the scales of `coeffs` within `band` into a power time series.
+    ///
+    /// Rows are brought to L1 normalization, weighted by the `d(ln a)` spacing of the scales and
+    /// divided by the admissibility constant of the wavelet, like the double-integral
+    /// reconstruction. The result therefore does not depend on `l1_norm` or on the scale density,
+    /// and summed over all scales it measures the power of the positive-frequency content
+    /// of the signal at every sample: a real sinusoid of amplitude `A` within the band
+    /// contributes `A² / 4`.
+    ///
+    /// # Arguments
+    /// * `coeffs` - Output of [`execute`](CwtExecutor::execute).
+    /// * `band` - Averaged scales, given directly or by frequency.
+    /// * `exclude_coi` - Skips coefficients inside the cone of influence, so samples near
+    ///   the edges sum fewer scales.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `coeffs` does not have one row per scale of equal length,
+    /// or if `band` is invalid or contains no scale.
+    fn scale_averaged_power(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        band: ScaleBand,
+        exclude_coi: bool,
+    ) -> Result<Vec<T>, ScaletError>;
+    /// Computes the cross-wavelet transform `W_xy = W_x · conj(W_y)` of two real signals.
+    ///
+    /// Both signals are transformed with one forward FFT each, and every scale filter is built
+    /// once and applied to both spectra. Besides the coefficients it returns the cross-wavelet
+    /// power, the phase difference and the time lag it implies at every scale.
+    ///
+    /// # Arguments
+    /// * `x` - First signal, the phase reference.
+    /// * `y` - Second signal, of the same length as `x`.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if the signals differ in length, are empty or longer than
+    /// the executor length.
+    fn cross_wavelet(&self, x: &[T], y: &[T]) -> Result<CrossWavelet<T>, ScaletError>;
+    /// Computes the wavelet coherence of two real signals, following Grinsted et al.
+    ///
+    /// `R² = |S(W_xy)|² / (S(|W_x|²) · S(|W_y|²))`, where `S` smooths in time with a Gaussian
+    /// as wide as the wavelet at each scale, then across scales with a boxcar whose width follows
+    /// the bandwidth of the wavelet. Coefficients are compared in L1 normalization, so the result
+    /// does not depend on `l1_norm`. Rows line up with [`view_scales`](CwtExecutor::view_scales).
+    ///
+    /// # Arguments
+    /// * `x` - First signal, the phase reference.
+    /// * `y` - Second signal, of the same length as `x`.
+    /// * `options` - Width of the time and scale smoothing.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if the signals differ in length, are empty or longer than
+    /// the executor length, or if the smoothing widths are invalid.
+    fn coherence(
+        &self,
+        x: &[T],
+        y: &[T],
+        options: CoherenceOptions,
+    ) -> Result<Coherence<T>, ScaletError>;
+    /// Extracts ridges of maximum energy from `coeffs`, one after another.
+    ///
+    /// Each ridge is the path through the scalogram that minimizes the negative log-energy
+    /// along it plus a penalty on the squared scale jump, in octaves, between neighbouring
+    /// samples, found by dynamic programming. After a ridge is extracted, its neighborhood is
+    /// removed from the energy so the next one follows another component.
+    ///
+    /// # Arguments
+    /// * `coeffs` - Output of [`execute`](CwtExecutor::execute).
+    /// * `options` - Number of ridges, smoothness and frequency conversion.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `coeffs` does not have one row per scale of equal length,
+    /// or if the options are out of range.
+    fn ridges(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        options: RidgeOptions,
+    ) -> Result<Vec<Ridge<T>>, ScaletError>;
+    /// Returns the phase of `coeffs` in radians, unwrapped along time.
+    ///
+    /// Each row starts at the wrapped phase of its first coefficient and accumulates the phase
+    /// increments between neighbouring samples, which are free of wrap-around as long as
+    /// the phase advances by less than half a turn per sample.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `coeffs` does not have one row per scale of equal length.
+    fn instantaneous_phase(&self, coeffs: &[Vec<Complex<T>>]) -> Result<Vec<Vec<T>>, ScaletError>;
+    /// Returns the instantaneous frequency of `coeffs` in Hz, the time derivative of the phase.
+    ///
+    /// The derivative is a central difference of the phase taken from the product of
+    /// neighbouring coefficients, so it needs no unwrapping, and one-sided at the edges.
+    /// Frequencies above the Nyquist frequency alias.
+    ///
+    /// # Arguments
+    /// * `coeffs` - Output of [`execute`](CwtExecutor::execute).
+    /// * `sampling_frequency` - Sampling frequency of the input in Hz.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `coeffs` does not have one row per scale of equal length,
+    /// or if `sampling_frequency` is not positive.
+    fn instantaneous_frequency(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        sampling_frequency: T,
+    ) -> Result<Vec<Vec<T>>, ScaletError>;
+    /// Returns the group delay of `coeffs` in seconds from the start of the signal.
+    ///
+    /// The delay is `t - dφ/dω`, with the phase derivative taken across neighbouring scales at
+    /// their center angular frequencies. Differences are taken in `ln ω`, which is uniform on
+    /// a logarithmic scale grid, and any other monotonic grid is handled as well. For an impulse
+    /// at `t0` every coefficient reports `t0`.
+    ///
+    /// # Arguments
+    /// * `coeffs` - Output of [`execute`](CwtExecutor::execute).
+    /// * `sampling_frequency` - Sampling frequency of the input in Hz.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `coeffs` does not have one row per scale of equal length,
+    /// if the executor has a single scale, or if `sampling_frequency` is not positive.
+    fn group_delay(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        sampling_frequency: T,
+    ) -> Result<Vec<Vec<T>>, ScaletError>;
+    /// Reconstructs the real signal from the coefficients selected by `mask`.
+    ///
+    /// Coefficients are multiplied by the mask, one for selected ones and zero elsewhere,
+    /// then inverted with [`inverse`](CwtExecutor::inverse). Soft weights avoid the ringing
+    /// a sharp region boundary introduces.
+    ///
+    /// # Arguments
+    /// * `coeffs` - Output of [`execute`](CwtExecutor::execute).
+    /// * `mask` - Kept region as a boolean or weight matrix, or a time–frequency polygon.
+    /// * `formula` - Inversion formula, see [`ReconstructionFormula`].
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `coeffs` or the mask do not have one row per scale of equal
+    /// length, if the polygon is invalid, or if the inversion fails.
+    fn masked_inverse(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        mask: TimeFrequencyMask<'_, T>,
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<T>, ScaletError>;
+    /// Reconstructs the real signal without the region selected by `mask`, the complement
+    /// of [`masked_inverse`](Self::masked_inverse). Weights `w` become `1 - w`.
+    fn remove_region(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        mask: TimeFrequencyMask<'_, T>,
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<T>, ScaletError>;
+    /// Reconstructs the real signal from the scales within `band` only, e.g. between two
+    /// frequencies with [`ScaleBand::Frequencies`].
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `coeffs` does not have one row per scale of equal length,
+    /// if `band` is invalid or contains no scale, or if the inversion fails.
+    fn band_pass(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        band: ScaleBand,
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<T>, ScaletError>;
+    /// Estimates the noise level of every scale of `coeffs`, the standard deviation
+    /// of the real and imaginary parts of noise-only coefficients.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `coeffs` does not have one row per scale of equal length,
+    /// or if the estimate is out of range.
+    fn noise_levels(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        noise: NoiseEstimate,
+    ) -> Result<Vec<T>, ScaletError>;
+    /// Denoises the signal behind `coeffs` by thresholding and reconstructs it.
+    ///
+    /// Every scale gets a threshold, by default the universal threshold of its noise level
+    /// from [`noise_levels`](Self::noise_levels). Coefficients whose magnitude falls below
+    /// it are zeroed and the others shrunk according to the [`Thresholding`](crate::Thresholding) rule,
+    /// keeping their phase. The result is inverted with [`inverse`](CwtExecutor::inverse).
+    ///
+    /// # Arguments
+    /// * `coeffs` - Output of [`execute`](CwtExecutor::execute).
+    /// * `options` - Noise estimate, thresholds, shrinkage and reconstruction.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if `coeffs` or the per-scale thresholds do not match the scales,
+    /// if the options are out of range, or if the inversion fails.
+    fn denoise(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        options: DenoiseOptions<'_, T>,
+    ) -> Result<Vec<T>, ScaletError>;
+}
+
+impl<T: CwtSample> CwtAnalysis<T> for CommonCwtExecutor<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    fn e_folding_times(&self) -> Result<Vec<T>, ScaletError> {
+        let ratio = self.e_folding_ratio()?;
+        let mut times = try_vec![T::zero(); self.scales.len()];
+        for (dst, &scale) in times.iter_mut().zip(self.scales.iter()) {
+            *dst = scale * ratio;
+        }
+        Ok(times)
+    }
+
+    fn cone_of_influence(&self, length: usize) -> Result<Vec<T>, ScaletError> {
+        let ratio = self.e_folding_ratio()?;
+        let mut cone = self.edge_distances(length)?;
+        for v in cone.iter_mut() {
+            *v = *v / ratio;
+        }
+        Ok(cone)
+    }
+
+    fn cone_of_influence_mask(&self, length: usize) -> Result<Vec<Vec<bool>>, ScaletError> {
+        let distances = self.edge_distances(length)?;
+        let times = self.e_folding_times()?;
+        let mut mask = try_vec![try_vec![false; length]; self.scales.len()];
+        for (row, &time) in mask.iter_mut().zip(times.iter()) {
+            for (dst, &distance) in row.iter_mut().zip(distances.iter()) {
+                *dst = distance < time;
+            }
+        }
+        Ok(mask)
+    }
+
+    fn significance(
+        &self,
+        input: &[T],
+        coeffs: &[Vec<Complex<T>>],
+        options: SignificanceOptions,
+    ) -> Result<Significance<T>, ScaletError> {
+        self.significance_impl(input, coeffs, options)
+    }
+
+    fn global_significance(
+        &self,
+        input: &[T],
+        samples: Option<&[usize]>,
+        options: SignificanceOptions,
+    ) -> Result<Vec<T>, ScaletError> {
+        self.global_significance_impl(input, samples, options)
+    }
+
+    fn global_wavelet_spectrum(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        exclude_coi: bool,
+    ) -> Result<GlobalSpectrum<T>, ScaletError> {
+        self.global_wavelet_spectrum_impl(coeffs, exclude_coi)
+    }
+
+    fn scale_averaged_power(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        band: ScaleBand,
+        exclude_coi: bool,
+    ) -> Result<Vec<T>, ScaletError> {
+        self.scale_averaged_power_impl(coeffs, band, exclude_coi)
+    }
+
+    fn cross_wavelet(&self, x: &[T], y: &[T]) -> Result<CrossWavelet<T>, ScaletError> {
+        self.cross_wavelet_impl(x, y)
+    }
+
+    fn coherence(
+        &self,
+        x: &[T],
+        y: &[T],
+        options: CoherenceOptions,
+    ) -> Result<Coherence<T>, ScaletError> {
+        self.coherence_impl(x, y, options)
+    }
+
+    fn ridges(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        options: RidgeOptions,
+    ) -> Result<Vec<Ridge<T>>, ScaletError> {
+        self.ridges_impl(coeffs, options)
+    }
+
+    fn instantaneous_phase(&self, coeffs: &[Vec<Complex<T>>]) -> Result<Vec<Vec<T>>, ScaletError> {
+        self.instantaneous_phase_impl(coeffs)
+    }
+
+    fn instantaneous_frequency(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        sampling_frequency: T,
+    ) -> Result<Vec<Vec<T>>, ScaletError> {
+        self.instantaneous_frequency_impl(coeffs, sampling_frequency)
+    }
+
+    fn group_delay(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        sampling_frequency: T,
+    ) -> Result<Vec<Vec<T>>, ScaletError> {
+        self.group_delay_impl(coeffs, sampling_frequency)
+    }
+
+    fn masked_inverse(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        mask: TimeFrequencyMask<'_, T>,
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<T>, ScaletError> {
+        self.masked_inverse_impl(coeffs, mask, false, formula)
+    }
+
+    fn remove_region(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        mask: TimeFrequencyMask<'_, T>,
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<T>, ScaletError> {
+        self.masked_inverse_impl(coeffs, mask, true, formula)
+    }
+
+    fn band_pass(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        band: ScaleBand,
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<T>, ScaletError> {
+        self.band_pass_impl(coeffs, band, formula)
+    }
+
+    fn noise_levels(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        noise: NoiseEstimate,
+    ) -> Result<Vec<T>, ScaletError> {
+        self.noise_levels_impl(coeffs, noise)
+    }
+
+    fn denoise(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        options: DenoiseOptions<'_, T>,
+    ) -> Result<Vec<T>, ScaletError> {
+        self.denoise_impl(coeffs, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CwtOptions, CwtWavelet, MorletWavelet, PaddingMode, Scalet};
+    use std::sync::Arc;
+
+    fn morlet() -> Arc<dyn CwtWavelet<f64> + Send + Sync> {
+        Arc::new(MorletWavelet::default())
+    }
+
+    fn analysis(
+        length: usize,
+        padding: PaddingMode,
+    ) -> Result<Arc<dyn CwtAnalysis<f64> + Send + Sync>, ScaletError> {
+        Scalet::make_cwt_f64(
+            morlet(),
+            length,
+            CwtOptions {
+                padding,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn morlet_e_folding_time_is_sqrt2_scale() -> Result<(), ScaletError> {
+        let executor = analysis(300, PaddingMode::Reflect)?;
+        let times = executor.e_folding_times()?;
+        for (&time, &scale) in times.iter().zip(executor.view_scales()) {
+            let ratio = time / scale;
+            assert!(
+                (ratio - std::f64::consts::SQRT_2).abs() < 1e-3,
+                "ratio {ratio}"
+            );
+        }
+        let cone = executor.cone_of_influence(300)?;
+        for (t, &value) in cone.iter().enumerate() {
+            let distance = t.min(299 - t) as f64;
+            assert!((value * std::f64::consts::SQRT_2 - distance).abs() < 1e-3 * (1. + distance));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn periodic_padding_has_no_edge_region() -> Result<(), ScaletError> {
+        // 256 samples fill the 512-point buffer with exactly two periods.
+        let executor = analysis(256, PaddingMode::Periodic)?;
+        assert!(
+            executor
+                .cone_of_influence(256)?
+                .iter()
+                .all(|v| v.is_infinite())
+        );
+        let mask = executor.cone_of_influence_mask(256)?;
+        assert!(mask.iter().flatten().all(|&inside| !inside));
+        // Otherwise the wrap of the buffer lies beyond the padding, outside the signal.
+        let periodic = analysis(300, PaddingMode::Periodic)?.cone_of_influence(300)?;
+        let reflect = analysis(300, PaddingMode::Reflect)?.cone_of_influence(300)?;
+        assert!(periodic.iter().zip(reflect.iter()).all(|(p, r)| p > r));
+        Ok(())
+    }
+
+    #[test]
+    fn analysis_executor_upcasts_to_the_transform() -> Result<(), ScaletError> {
+        let executor = analysis(300, PaddingMode::Reflect)?;
+        let transform: Arc<dyn CwtExecutor<f64> + Send + Sync> = executor.clone();
+        let signal = (0..300)
+            .map(|i| (0.2 * i as f64).sin())
+            .collect::<Vec<f64>>();
+        assert_eq!(transform.execute(&signal)?, executor.execute(&signal)?);
+        Ok(())
+    }
+}
@@ -70,6 +70,14 @@ impl SpectrumArithmetic<f32> for AvxSpectrumF32 {
     ) {
         unsafe { self.mul_by_b_conj_normalize_impl(dst, input, other, normalize_value) }
     }
+
+    fn power(&self, dst: &mut [f32], src: &[Complex<f32>]) {
+        unsafe { self.power_impl::<false>(dst, src) }
+    }
+
+    fn magnitude(&self, dst: &mut [f32], src: &[Complex<f32>]) {
+        unsafe { self.power_impl::<true>(dst, src) }
+    }
 }
 
 impl AvxSpectrumF32 {
@@ -127,7 +135,10 @@ impl AvxSpectrumF32 {
                 let v0 = _mm_loadu_ps(input.as_ptr().cast());
                 let v1 = _mm_loadu_ps(other.as_ptr().cast());
 
-                let p1 = _mm_fcmul_a_by_b_conj(v0, v1);
+                let p1 = _mm_mul_ps(
+                    _mm_fcmul_a_by_b_conj(v0, v1),
+                    _mm256_castps256_ps128(v_norm_factor),
+                );
                 _mm_storeu_ps(dst.as_mut_ptr().cast(), p1);
             }
 
@@ -143,9 +154,41 @@ impl AvxSpectrumF32 {
                 let v0 = _mm_castsi128_ps(_mm_loadu_si64((input as *const Complex<f32>).cast()));
                 let v1 = _mm_castsi128_ps(_mm_loadu_si64((other as *const Complex<f32>).cast()));
 
-                let p1 = _mm_fcmul_a_by_b_conj(v0, v1);
+                let p1 = _mm_mul_ps(
+                    _mm_fcmul_a_by_b_conj(v0, v1),
+                    _mm256_castps256_ps128(v_norm_factor),
+                );
                 _mm_storeu_si64((dst as *mut Complex<f32>).cast(), _mm_castps_si128(p1));
             }
         }
     }
+
+    /// |src|², or |src| when `ROOT` is set.
+    #[target_feature(enable = "avx2", enable = "fma")]
+    fn power_impl<const ROOT: bool>(&self, dst: &mut [f32], src: &[Complex<f32>]) {
+        unsafe {
+            for (dst, src) in dst.chunks_exact_mut(8).zip(src.chunks_exact(8)) {
+                let v0 = _mm256_loadu_ps(src.as_ptr().cast());
+                let v1 = _mm256_loadu_ps(src.get_unchecked(4..).as_ptr().cast());
+
+                // Horizontal sums come out as [c0, c1, c4, c5 | c2, c3, c6, c7].
+                let sum = _mm256_hadd_ps(_mm256_mul_ps(v0, v0), _mm256_mul_ps(v1, v1));
+                let mut p =
+                    _mm256_castpd_ps(_mm256_permute4x64_pd::<0b1101_1000>(_mm256_castps_pd(sum)));
+                if ROOT {
+                    p = _mm256_sqrt_ps(p);
+                }
+
+                _mm256_storeu_ps(dst.as_mut_ptr(), p);
+            }
+
+            let dst_rem = dst.chunks_exact_mut(8).into_remainder();
+            let src_rem = src.chunks_exact(8).remainder();
+
+            for (dst, src) in dst_rem.iter_mut().zip(src_rem.iter()) {
+                let p = src.norm_sqr();
+                *dst = if ROOT { p.sqrt() } else { p };
+            }
+        }
+    }
 }
@@ -70,6 +70,14 @@ impl SpectrumArithmetic<f64> for AvxSpectrumF64 {
             self.mul_by_b_conj_normalize_impl(dst, input, other, normalize_value);
         }
     }
+
+    fn power(&self, dst: &mut [f64], src: &[Complex<f64>]) {
+        unsafe { self.power_impl::<false>(dst, src) }
+    }
+
+    fn magnitude(&self, dst: &mut [f64], src: &[Complex<f64>]) {
+        unsafe { self.power_impl::<true>(dst, src) }
+    }
 }
 
 impl AvxSpectrumF64 {
@@ -148,4 +156,32 @@ impl AvxSpectrumF64 {
             }
         }
     }
+
+    /// |src|², or |src| when `ROOT` is set.
+    #[target_feature(enable = "avx2", enable = "fma")]
+    fn power_impl<const ROOT: bool>(&self, dst: &mut [f64], src: &[Complex<f64>]) {
+        unsafe {
+            for (dst, src) in dst.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+                let v0 = _mm256_loadu_pd(src.as_ptr().cast());
+                let v1 = _mm256_loadu_pd(src.get_unchecked(2..).as_ptr().cast());
+
+                // Horizontal sums come out as [c0, c2, c1, c3].
+                let sum = _mm256_hadd_pd(_mm256_mul_pd(v0, v0), _mm256_mul_pd(v1, v1));
+                let mut p = _mm256_permute4x64_pd::<0b1101_1000>(sum);
+                if ROOT {
+                    p = _mm256_sqrt_pd(p);
+                }
+
+                _mm256_storeu_pd(dst.as_mut_ptr(), p);
+            }
+
+            let dst_rem = dst.chunks_exact_mut(4).into_remainder();
+            let src_rem = src.chunks_exact(4).remainder();
+
+            for (dst, src) in dst_rem.iter_mut().zip(src_rem.iter()) {
+                let p = src.norm_sqr();
+                *dst = if ROOT { p.sqrt() } else { p };
+            }
+        }
+    }
 }
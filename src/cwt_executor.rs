@@ -27,24 +27,87 @@
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 use crate::err::try_vec;
+use crate::icwt::{
+    double_integral_constant, log_scale_weights, negative_double_integral_constant,
+    single_integral_constant,
+};
+use crate::mla::fmla;
+use crate::padding::{PaddingMode, pad_signal};
 use crate::sample::CwtSample;
+use crate::scale_bounds::WaveletSearch;
 use crate::spetrum_arith::SpectrumArithmetic;
-use crate::{CwtExecutor, CwtWavelet, ScaletError};
+use crate::time_support::e_folding_ratio;
+use crate::{CwtExecutor, CwtWavelet, ReconstructionFormula, ScaletError};
+use novtb::{ParallelZonedIterator, TbSliceMut, ThreadPool};
 use num_complex::Complex;
 use num_traits::{AsPrimitive, Zero};
-use std::sync::Arc;
-use zaft::FftExecutor;
+use std::sync::{Arc, OnceLock};
+use zaft::{FftExecutor, R2CFftExecutor};
 
 pub(crate) struct CommonCwtExecutor<T> {
     pub(crate) wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
     pub(crate) fft_forward: Arc<dyn FftExecutor<T> + Send + Sync>,
     pub(crate) fft_inverse: Arc<dyn FftExecutor<T> + Send + Sync>,
+    /// Real-to-complex plan, present only for analytic wavelets whose negative-frequency
+    /// half is zero, so the redundant half of a real signal spectrum is never needed.
+    pub(crate) fft_real: Option<Arc<dyn R2CFftExecutor<T> + Send + Sync>>,
     pub(crate) spectrum_arithmetic: Arc<dyn SpectrumArithmetic<T> + Send + Sync>,
     pub(crate) scales: Vec<T>,
     pub(crate) psi: Vec<T>,
+    /// Maximum length of the signal accepted by the executor.
+    pub(crate) length: usize,
+    /// FFT length, the maximum signal length plus padding, rounded up to a fast size.
     pub(crate) execution_length: usize,
+    pub(crate) padding: PaddingMode,
+    /// Number of leading frequency bins the wavelet filters are evaluated at,
+    /// `execution_length / 2 + 1` for analytic wavelets, `execution_length` otherwise.
+    pub(crate) filter_length: usize,
     pub(crate) l1_norm: bool,
     pub(crate) scratch_length: usize,
+    /// Worker threads the scales are spread across, built once with the executor.
+    pub(crate) pool: ThreadPool,
+    /// Tolerances of the searches over the wavelet's frequency response.
+    pub(crate) wavelet_search: WaveletSearch,
+    pub(crate) single_integral_constant: OnceLock<Complex<T>>,
+    pub(crate) double_integral_constant: OnceLock<T>,
+    /// Double-integral constant of the negative half-axis, zero when the wavelet
+    /// does not capture negative frequencies.
+    pub(crate) negative_double_integral_constant: OnceLock<T>,
+    /// E-folding time per unit scale, see [`e_folding_ratio`].
+    pub(crate) e_folding_ratio: OnceLock<T>,
+    /// Frequency-domain filters of all scales, `filter_length` per scale,
+    /// present only when precomputation was requested.
+    pub(crate) filter_bank: Option<Vec<Complex<T>>>,
+}
+
+/// Buffers of the scale loop, split from a single complex allocation or from caller scratch.
+pub(crate) struct ScaleWorkspace<'a, T> {
+    pub(crate) scratch: &'a mut [Complex<T>],
+    /// Dilated angular frequencies, empty when filters are precomputed.
+    pub(crate) current_psi: &'a mut [T],
+    /// Filter of the current scale, empty when filters are precomputed.
+    pub(crate) filter: &'a mut [Complex<T>],
+    /// Padded coefficients, used when the signal is shorter than the FFT length.
+    pub(crate) padded_row: &'a mut [Complex<T>],
+}
+
+/// Real-valued reduction applied to every row of coefficients.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum RealOutput {
+    /// Squared magnitude `|W|²`.
+    Power,
+    /// Magnitude `|W|`.
+    Magnitude,
+}
+
+/// Views complex values as interleaved real and imaginary parts.
+#[inline]
+fn as_real_mut<T>(slice: &mut [Complex<T>]) -> &mut [T] {
+    // SAFETY: `Complex<T>` is `#[repr(C)]` with exactly two fields `re` and `im` of type `T`,
+    // so it has the size of `[T; 2]`, no padding, and the alignment of `T`. A pointer to
+    // `len` complex values is therefore valid and aligned for `2 * len` values of `T`, and the
+    // exclusive borrow of `slice` is carried over to the returned slice.
+    unsafe { std::slice::from_raw_parts_mut(slice.as_mut_ptr().cast(), slice.len() * 2) }
 }
 
 impl<T: CwtSample> CommonCwtExecutor<T>
@@ -52,85 +115,715 @@ where
     f64: AsPrimitive<T>,
     usize: AsPrimitive<T>,
 {
-    fn execute_impl(
+    /// Builds the frequency-domain wavelet filter for a single scale into `dst`.
+    ///
+    /// `current_psi` is a workspace of `filter_length` angular frequencies.
+    pub(crate) fn make_scale_filter(
+        &self,
+        scale: T,
+        current_psi: &mut [T],
+        dst: &mut [Complex<T>],
+    ) -> Result<(), ScaletError> {
+        // Adjust the pre-calculated base phases (self.psi) by the current scale 'a'.
+        // This implements the dilation property of the wavelet in the frequency domain.
+        // The frequency-domain wavelet is scaled by 1/a, and its amplitude is scaled by 'a'.
+        for (dst, &psi) in current_psi.iter_mut().zip(self.psi.iter()) {
+            *dst = psi * scale;
+        }
+
+        // Generate the final complex FFT filter for the current scale 'a'.
+        self.wavelet.make_wavelet_into(current_psi, dst)
+    }
+
+    /// Generates the filters of all scales into a single contiguous bank.
+    pub(crate) fn make_filter_bank(&self) -> Result<Vec<Complex<T>>, ScaletError> {
+        let mut bank = try_vec![Complex::zero(); self.scales.len() * self.filter_length];
+        let mut current_psi = try_vec![T::zero(); self.filter_length];
+        for (&scale, dst) in self
+            .scales
+            .iter()
+            .zip(bank.chunks_exact_mut(self.filter_length))
+        {
+            self.make_scale_filter(scale, &mut current_psi, dst)?;
+        }
+        Ok(bank)
+    }
+
+    /// Frequency-domain filter of the scale at `index`, borrowed from the filter bank
+    /// when it is present, otherwise generated into the workspace.
+    pub(crate) fn scale_filter<'a>(
+        &'a self,
+        index: usize,
+        current_psi: &mut [T],
+        filter: &'a mut [Complex<T>],
+    ) -> Result<&'a [Complex<T>], ScaletError> {
+        match &self.filter_bank {
+            Some(bank) => Ok(&bank[index * self.filter_length..(index + 1) * self.filter_length]),
+            None => {
+                self.make_scale_filter(self.scales[index], current_psi, filter)?;
+                Ok(filter)
+            }
+        }
+    }
+
+    /// Length of the complex buffer [`split_workspace`](Self::split_workspace) expects.
+    pub(crate) fn workspace_length(&self) -> usize {
+        let filter = if self.filter_bank.is_some() {
+            0
+        } else {
+            self.filter_length + self.filter_length.div_ceil(2)
+        };
+        self.scratch_length + filter + self.execution_length
+    }
+
+    pub(crate) fn make_workspace(&self) -> Result<Vec<Complex<T>>, ScaletError> {
+        Ok(try_vec![Complex::zero(); self.workspace_length()])
+    }
+
+    /// Splits at least [`workspace_length`](Self::workspace_length) values into scale loop buffers.
+    pub(crate) fn split_workspace<'a>(
+        &self,
+        workspace: &'a mut [Complex<T>],
+    ) -> ScaleWorkspace<'a, T> {
+        let (scratch, rest) = workspace.split_at_mut(self.scratch_length);
+        let (padded_row, rest) = rest.split_at_mut(self.execution_length);
+        let (filter, current_psi) = if self.filter_bank.is_some() {
+            (&mut [][..], &mut [][..])
+        } else {
+            let (filter, rest) = rest.split_at_mut(self.filter_length);
+            let current_psi = &mut as_real_mut(rest)[..self.filter_length];
+            (filter, current_psi)
+        };
+        ScaleWorkspace {
+            scratch,
+            current_psi,
+            filter,
+            padded_row,
+        }
+    }
+
+    /// Number of bytes taken by the per-scale filters when they are precomputed.
+    pub(crate) fn filter_bank_size(&self) -> usize {
+        self.scales.len() * self.filter_length * size_of::<Complex<T>>()
+    }
+
+    fn single_integral_constant(&self) -> Result<Complex<T>, ScaletError> {
+        if let Some(&c) = self.single_integral_constant.get() {
+            return Ok(c);
+        }
+        let c = single_integral_constant(self.wavelet.as_ref())?;
+        Ok(*self.single_integral_constant.get_or_init(|| c))
+    }
+
+    pub(crate) fn double_integral_constant(&self) -> Result<T, ScaletError> {
+        if let Some(&c) = self.double_integral_constant.get() {
+            return Ok(c);
+        }
+        let c = double_integral_constant(self.wavelet.as_ref())?;
+        Ok(*self.double_integral_constant.get_or_init(|| c))
+    }
+
+    fn negative_double_integral_constant(&self) -> Result<T, ScaletError> {
+        if let Some(&c) = self.negative_double_integral_constant.get() {
+            return Ok(c);
+        }
+        let c = negative_double_integral_constant(
+            self.wavelet.as_ref(),
+            self.double_integral_constant()?,
+        )?;
+        Ok(*self.negative_double_integral_constant.get_or_init(|| c))
+    }
+
+    pub(crate) fn e_folding_ratio(&self) -> Result<T, ScaletError> {
+        if let Some(&c) = self.e_folding_ratio.get() {
+            return Ok(c);
+        }
+        let c = e_folding_ratio(self.wavelet.as_ref())?;
+        Ok(*self.e_folding_ratio.get_or_init(|| c))
+    }
+
+    /// Distance, in samples, of every column of a `length`-sample signal to its nearest edge.
+    ///
+    /// Periodic padding treats the signal as one period, so its first and last sample are
+    /// neighbours and the only edge left is the seam where the FFT buffer wraps, absent when
+    /// the buffer holds a whole number of periods. Every other padding mode joins the data to
+    /// an artificial extension at the first and last sample, and without padding the circular
+    /// convolution wraps them onto each other, so edges are the signal boundaries.
+    pub(crate) fn edge_distances(&self, length: usize) -> Result<Vec<T>, ScaletError> {
+        self.check_length(length)?;
+        let mut distances = try_vec![T::zero(); length];
+        match self.padding {
+            PaddingMode::Periodic if self.execution_length.is_multiple_of(length) => {
+                distances.fill(T::INFINITY);
+            }
+            PaddingMode::Periodic => {
+                let left = self.pad_left(length);
+                for (t, dst) in distances.iter_mut().enumerate() {
+                    *dst = (left + t).min(self.execution_length - left - t).as_();
+                }
+            }
+            PaddingMode::None
+            | PaddingMode::Reflect
+            | PaddingMode::Symmetric
+            | PaddingMode::Zero
+            | PaddingMode::Edge => {
+                for (t, dst) in distances.iter_mut().enumerate() {
+                    *dst = t.min(length - 1 - t).as_();
+                }
+            }
+        }
+        Ok(distances)
+    }
+
+    /// Transforms the input signal into the frequency domain (Spectral Domain), in place.
+    /// This is the first step of the FFT-based convolution theorem.
+    pub(crate) fn forward_spectrum(
         &self,
         signal_fft: &mut [Complex<T>],
-    ) -> Result<Vec<Vec<Complex<T>>>, ScaletError> {
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), ScaletError> {
         if self.execution_length != signal_fft.len() {
             return Err(ScaletError::InvalidInputSize(
                 self.execution_length,
                 signal_fft.len(),
             ));
         }
+        self.fft_forward
+            .execute_with_scratch(signal_fft, scratch)
+            .map_err(|x| ScaletError::FftError(x.to_string()))
+    }
 
-        let mut scratch = try_vec![Complex::zero(); self.scratch_length];
+    /// Number of leading bins [`real_spectrum`](Self::real_spectrum) produces.
+    pub(crate) fn real_spectrum_length(&self) -> usize {
+        match &self.fft_real {
+            Some(fft_real) => fft_real.complex_length(),
+            None => self.execution_length,
+        }
+    }
 
-        // 1. Transform the input signal into the frequency domain (Spectral Domain).
-        // This is the first step of the FFT-based convolution theorem.
-        self.fft_forward
-            .execute_with_scratch(signal_fft, &mut scratch)
-            .map_err(|x| ScaletError::FftError(x.to_string()))?;
+    /// Spectrum of a real, already padded signal, `dst` takes
+    /// [`real_spectrum_length`](Self::real_spectrum_length) bins.
+    ///
+    /// With an analytic wavelet only the non-negative half is needed, which is exactly what a
+    /// real-to-complex FFT produces, otherwise the signal is promoted to complex.
+    pub(crate) fn real_spectrum(
+        &self,
+        padded: &[T],
+        dst: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), ScaletError> {
+        match &self.fft_real {
+            Some(fft_real) => fft_real
+                .execute_with_scratch(padded, dst, scratch)
+                .map_err(|x| ScaletError::FftError(x.to_string())),
+            None => {
+                for (dst, &src) in dst.iter_mut().zip(padded.iter()) {
+                    *dst = Complex::new(src, T::zero());
+                }
+                self.forward_spectrum(dst, scratch)
+            }
+        }
+    }
 
-        // Frequency vector
-        let scales = self.view_scales();
+    /// Convolves the signal spectrum with a single scale filter and writes
+    /// the complex CWT coefficients Wx(a, b) into `dst`.
+    pub(crate) fn scale_coefficients(
+        &self,
+        signal_fft: &[Complex<T>],
+        wavelet_fft: &[Complex<T>],
+        scale: T,
+        dst: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), ScaletError> {
+        // --- Step 2: Perform Convolution via Frequency-Domain Multiplication ---
 
-        // Initialize temporary vectors and the final result structure.
-        // current_psi: Workspace for the wavelet filter in the frequency domain for the current scale.
-        let mut current_psi = try_vec![T::zero(); self.execution_length];
-        // result: The final CWT drawing [num_scales][signal_length], storing complex coefficients.
-        let mut result = try_vec![try_vec![Complex::zero(); self.execution_length]; scales.len()];
+        // Multiply the Signal FFT by the (conjugate of the) Wavelet FFT element-wise.
+        // This is the core convolution theorem: IFFT(F(x) * F(y)) = x * y
+        // additionally we'll normalize in this step as a part of optimization
 
-        for (&scale, v_dst) in scales.iter().zip(result.iter_mut()) {
-            // --- Step 1: Prepare Wavelet Filter for Convolution ---
+        // Calculate the overall normalization factor (including the IFFT factor and CWT factor).
+        let norm_factor = self.normalization(scale);
 
-            // Adjust the pre-calculated base phases (self.psi) by the current scale 'a'.
-            // This implements the dilation property of the wavelet in the frequency domain.
-            // The frequency-domain wavelet is scaled by 1/a, and its amplitude is scaled by 'a'.
-            for (dst, &psi) in current_psi.iter_mut().zip(self.psi.iter()) {
-                *dst = psi * scale;
-            }
+        // Filters of analytic wavelets cover only the non-negative frequencies,
+        // the remaining bins of the product are zeros.
+        let bins = wavelet_fft.len();
+        let (product, negative) = dst.split_at_mut(bins);
+        // input * other.conj() * normalize_value
+        self.spectrum_arithmetic.mul_by_b_conj_normalize(
+            product,
+            &signal_fft[..bins],
+            wavelet_fft,
+            norm_factor,
+        );
+        negative.fill(Complex::zero());
 
-            // Generate the final complex FFT filter for the current scale 'a'.
-            let wavelet_fft = self.wavelet.make_wavelet(&current_psi)?;
+        // --- Step 3: Inverse Transform to the Time Domain ---
 
-            if wavelet_fft.len() != self.execution_length {
-                return Err(ScaletError::WaveletInvalidSize(
-                    self.execution_length,
-                    wavelet_fft.len(),
-                ));
-            }
+        // Perform the Inverse FFT (IFFT) to transform the resulting spectrum back to the time domain.
+        // The result in dst is the complex CWT coefficients Wx(a, b) at the current scale 'a'.
+        self.fft_inverse
+            .execute_with_scratch(dst, scratch)
+            .map_err(|x| ScaletError::FftError(x.to_string()))
+    }
+
+    /// Factor applied to the product of the signal spectrum and the filter at `scale`,
+    /// including the `1/N` of the unnormalized inverse FFT.
+    pub(crate) fn normalization(&self, scale: T) -> T {
+        if self.l1_norm {
+            // L1 Normalization (Amplitude/Area): Typically divides by 'a' (scale).
+            // This current implementation only corrects for the unscaled IFFT (1/N).
+            1.0f64.as_() / self.execution_length.as_()
+        } else {
+            // L2 Normalization (Energy)
+            1.0f64.as_() / (self.execution_length.as_() * scale.sqrt())
+        }
+    }
 
-            // --- Step 2: Perform Convolution via Frequency-Domain Multiplication ---
+    /// Factor that brings coefficients at `scale` back to L1 normalization,
+    /// L2 rows were scaled by 1/sqrt(a) in the forward transform.
+    pub(crate) fn l1_amplitude(&self, scale: T) -> T {
+        if self.l1_norm {
+            1.0f64.as_()
+        } else {
+            scale.sqrt()
+        }
+    }
 
-            // Multiply the Signal FFT by the (conjugate of the) Wavelet FFT element-wise.
-            // This is the core convolution theorem: IFFT(F(x) * F(y)) = x * y
-            // additionally we'll normalize in this step as a part of optimization
+    /// Computes the coefficients of the scale at `index` into `dst`.
+    fn execute_scale(
+        &self,
+        index: usize,
+        signal_fft: &[Complex<T>],
+        workspace: &mut ScaleWorkspace<'_, T>,
+        dst: &mut [Complex<T>],
+    ) -> Result<(), ScaletError> {
+        let scale = self.scales[index];
 
-            // Calculate the overall normalization factor (including the IFFT factor and CWT factor).
-            let norm_factor = if self.l1_norm {
-                // L1 Normalization (Amplitude/Area): Typically divides by 'a' (scale).
-                // This current implementation only corrects for the unscaled IFFT (1/N).
-                1.0f64.as_() / v_dst.len().as_()
-            } else {
-                // L2 Normalization (Energy)
-                1.0f64.as_() / (v_dst.len().as_() * scale.sqrt())
-            };
+        // --- Step 1: Prepare Wavelet Filter for Convolution ---
+        let wavelet_fft = self.scale_filter(index, workspace.current_psi, workspace.filter)?;
 
-            // input * other.conj() * normalize_value
-            self.spectrum_arithmetic.mul_by_b_conj_normalize(
-                v_dst,
+        if dst.len() == self.execution_length {
+            self.scale_coefficients(signal_fft, wavelet_fft, scale, dst, workspace.scratch)
+        } else {
+            self.scale_coefficients(
                 signal_fft,
-                &wavelet_fft,
-                norm_factor,
+                wavelet_fft,
+                scale,
+                workspace.padded_row,
+                workspace.scratch,
+            )?;
+            dst.copy_from_slice(self.crop(workspace.padded_row, dst.len()));
+            Ok(())
+        }
+    }
+
+    /// Computes the power or magnitude of the scale at `index` into `dst`,
+    /// the complex coefficients only live in the workspace row.
+    fn execute_scale_real(
+        &self,
+        index: usize,
+        signal_fft: &[Complex<T>],
+        workspace: &mut ScaleWorkspace<'_, T>,
+        mode: RealOutput,
+        dst: &mut [T],
+    ) -> Result<(), ScaletError> {
+        let wavelet_fft = self.scale_filter(index, workspace.current_psi, workspace.filter)?;
+        self.scale_coefficients(
+            signal_fft,
+            wavelet_fft,
+            self.scales[index],
+            workspace.padded_row,
+            workspace.scratch,
+        )?;
+        let row = self.crop(workspace.padded_row, dst.len());
+        match mode {
+            RealOutput::Power => self.spectrum_arithmetic.power(dst, row),
+            RealOutput::Magnitude => self.spectrum_arithmetic.magnitude(dst, row),
+        }
+        Ok(())
+    }
+
+    fn execute_impl(
+        &self,
+        signal_fft: &[Complex<T>],
+        length: usize,
+    ) -> Result<Vec<Vec<Complex<T>>>, ScaletError> {
+        self.execute_rows(length, |index, workspace, dst| {
+            self.execute_scale(index, signal_fft, workspace, dst)
+        })
+    }
+
+    fn execute_real_impl(
+        &self,
+        signal_fft: &[Complex<T>],
+        length: usize,
+        mode: RealOutput,
+    ) -> Result<Vec<Vec<T>>, ScaletError> {
+        self.execute_rows(length, |index, workspace, dst| {
+            self.execute_scale_real(index, signal_fft, workspace, mode, dst)
+        })
+    }
+
+    /// Allocates `[num_scales][length]` rows and fills them with `run`, spread across the workers.
+    pub(crate) fn execute_rows<V: Copy + Zero + Send>(
+        &self,
+        length: usize,
+        run: impl Fn(usize, &mut ScaleWorkspace<'_, T>, &mut [V]) -> Result<(), ScaletError>
+        + Send
+        + Sync,
+    ) -> Result<Vec<Vec<V>>, ScaletError> {
+        self.execute_rows_with_buffer(length, 0, |index, workspace, _, dst| {
+            run(index, workspace, dst)
+        })
+    }
+
+    /// Same as [`execute_rows`](Self::execute_rows), every worker additionally owns
+    /// a buffer of `extra` values that `run` receives after the scale workspace.
+    pub(crate) fn execute_rows_with_buffer<V: Copy + Zero + Send>(
+        &self,
+        length: usize,
+        extra: usize,
+        run: impl Fn(
+            usize,
+            &mut ScaleWorkspace<'_, T>,
+            &mut [Complex<T>],
+            &mut [V],
+        ) -> Result<(), ScaletError>
+        + Send
+        + Sync,
+    ) -> Result<Vec<Vec<V>>, ScaletError> {
+        // result: The final CWT drawing [num_scales][signal_length].
+        let mut result = try_vec![try_vec![V::zero(); length]; self.scales.len()];
+
+        // Scales are independent, every worker owns its buffers and a disjoint set of rows.
+        let error = OnceLock::new();
+        let workspace_length = self.workspace_length();
+        result
+            .tb_par_chunks_exact_mut(1)
+            .for_each_enumerated_with_context(
+                &self.pool,
+                || -> Result<Vec<Complex<T>>, ScaletError> {
+                    Ok(try_vec![Complex::zero(); workspace_length + extra])
+                },
+                |index, workspace, rows| {
+                    let status = match workspace {
+                        Ok(workspace) => {
+                            let (workspace, buffer) = workspace.split_at_mut(workspace_length);
+                            run(
+                                index,
+                                &mut self.split_workspace(workspace),
+                                buffer,
+                                &mut rows[0],
+                            )
+                        }
+                        Err(err) => Err(err.clone()),
+                    };
+                    if let Err(err) = status {
+                        _ = error.set(err);
+                    }
+                },
             );
+        if let Some(err) = error.into_inner() {
+            return Err(err);
+        }
+
+        Ok(result)
+    }
+
+    /// Allocation-free scale loop writing rows of `length` into the flat `out`.
+    fn execute_into_impl(
+        &self,
+        signal_fft: &[Complex<T>],
+        length: usize,
+        out: &mut [Complex<T>],
+        workspace: &mut ScaleWorkspace<'_, T>,
+    ) -> Result<(), ScaletError> {
+        for (index, dst) in out.chunks_exact_mut(length).enumerate() {
+            self.execute_scale(index, signal_fft, workspace, dst)?;
+        }
+        Ok(())
+    }
+
+    /// Allocation-free power or magnitude of a real signal into the flat `out`.
+    fn execute_real_into_impl(
+        &self,
+        input: &[T],
+        out: &mut [T],
+        scratch: &mut [Complex<T>],
+        mode: RealOutput,
+    ) -> Result<(), ScaletError> {
+        self.check_length(input.len())?;
+        self.check_output(input.len(), out)?;
+        let (spectrum, padded, mut workspace) = self.split_scratch(scratch)?;
+        pad_signal(input, padded, self.pad_left(input.len()), self.padding);
+        let spectrum = &mut spectrum[..self.real_spectrum_length()];
+        self.real_spectrum(padded, spectrum, workspace.scratch)?;
+        for (index, dst) in out.chunks_exact_mut(input.len()).enumerate() {
+            self.execute_scale_real(index, spectrum, &mut workspace, mode, dst)?;
+        }
+        Ok(())
+    }
+
+    /// Power or magnitude of a real signal, spread across the workers.
+    fn execute_real(&self, input: &[T], mode: RealOutput) -> Result<Vec<Vec<T>>, ScaletError> {
+        let padded = self.pad(input)?;
+        let mut scratch = try_vec![Complex::zero(); self.scratch_length];
+        let mut signal_fft = try_vec![Complex::zero(); self.real_spectrum_length()];
+        self.real_spectrum(&padded, &mut signal_fft, &mut scratch)?;
+        self.execute_real_impl(&signal_fft, input.len(), mode)
+    }
+
+    /// Validates a batch of channels and returns their common length.
+    fn check_batch(&self, channels: &[&[T]]) -> Result<usize, ScaletError> {
+        let length = channels.first().map(|x| x.len()).unwrap_or(0);
+        self.check_length(length)?;
+        if let Some(channel) = channels.iter().find(|x| x.len() != length) {
+            return Err(ScaletError::InvalidInputSize(length, channel.len()));
+        }
+        Ok(length)
+    }
+
+    /// Transforms a block of channels, every scale filter is generated once and applied
+    /// to all channels of the block. `emit` receives the channel index within the block,
+    /// the scale index and the coefficients.
+    fn execute_batch_block(
+        &self,
+        channels: &[&[T]],
+        length: usize,
+        mut emit: impl FnMut(usize, usize, &[Complex<T>]),
+    ) -> Result<(), ScaletError> {
+        let mut workspace = self.make_workspace()?;
+        let workspace = self.split_workspace(&mut workspace);
+
+        let bins = self.real_spectrum_length();
+        let mut spectra = try_vec![Complex::zero(); channels.len() * bins];
+        let mut padded = try_vec![T::zero(); self.execution_length];
+        for (channel, spectrum) in channels.iter().zip(spectra.chunks_exact_mut(bins)) {
+            pad_signal(channel, &mut padded, self.pad_left(length), self.padding);
+            self.real_spectrum(&padded, spectrum, workspace.scratch)?;
+        }
+
+        for (index, &scale) in self.scales.iter().enumerate() {
+            let wavelet_fft = self.scale_filter(index, workspace.current_psi, workspace.filter)?;
+            for (channel, spectrum) in spectra.chunks_exact(bins).enumerate() {
+                self.scale_coefficients(
+                    spectrum,
+                    wavelet_fft,
+                    scale,
+                    workspace.padded_row,
+                    workspace.scratch,
+                )?;
+                emit(channel, index, self.crop(workspace.padded_row, length));
+            }
+        }
+        Ok(())
+    }
+
+    /// Spreads contiguous blocks of channels across the worker threads,
+    /// `out` holds `out_per_channel` values for every channel.
+    fn execute_batch_blocks<V: Send>(
+        &self,
+        channels: &[&[T]],
+        length: usize,
+        out: &mut [V],
+        out_per_channel: usize,
+        write: impl Fn(&mut [V], usize, usize, &[Complex<T>]) + Send + Sync,
+    ) -> Result<(), ScaletError> {
+        // Each worker gets a contiguous block of channels, so a filter is built once
+        // per worker and scale instead of once per channel.
+        let block = channels.len().div_ceil(self.pool.thread_count());
+        let error = OnceLock::new();
+        out.tb_par_chunks_mut(block * out_per_channel)
+            .for_each_enumerated(&self.pool, |index, dst| {
+                let start = index * block;
+                let end = (start + block).min(channels.len());
+                let status = self.execute_batch_block(
+                    &channels[start..end],
+                    length,
+                    |channel, scale, row| write(dst, channel, scale, row),
+                );
+                if let Err(err) = status {
+                    _ = error.set(err);
+                }
+            });
+        if let Some(err) = error.into_inner() {
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Validates the flat output of an `execute_into` call for a signal of `length`.
+    fn check_output<V>(&self, length: usize, out: &[V]) -> Result<(), ScaletError> {
+        let expected = self.scales.len() * length;
+        if out.len() != expected {
+            return Err(ScaletError::InvalidInputSize(expected, out.len()));
+        }
+        Ok(())
+    }
+
+    /// Lays out caller scratch as the signal spectrum, a real input buffer
+    /// and the scale loop workspace.
+    #[allow(clippy::type_complexity)]
+    fn split_scratch<'a>(
+        &self,
+        scratch: &'a mut [Complex<T>],
+    ) -> Result<(&'a mut [Complex<T>], &'a mut [T], ScaleWorkspace<'a, T>), ScaletError> {
+        let required = <Self as CwtExecutor<T>>::scratch_length(self);
+        if scratch.len() < required {
+            return Err(ScaletError::InvalidInputSize(required, scratch.len()));
+        }
+        let (spectrum, rest) = scratch.split_at_mut(self.execution_length);
+        let (real, rest) = rest.split_at_mut(self.execution_length.div_ceil(2));
+        let real = &mut as_real_mut(real)[..self.execution_length];
+        Ok((spectrum, real, self.split_workspace(rest)))
+    }
+
+    /// Validates the length of an input signal, anything from one sample
+    /// up to the executor length is accepted.
+    pub(crate) fn check_length(&self, length: usize) -> Result<(), ScaletError> {
+        if length == 0 {
+            return Err(ScaletError::ZeroBaseSized);
+        }
+        if length > self.length {
+            return Err(ScaletError::InvalidInputSize(self.length, length));
+        }
+        Ok(())
+    }
+
+    /// Offset of the first signal sample inside the padded buffer for a signal of `length`.
+    ///
+    /// Without padding the signal starts at zero and trailing zeros fill the FFT length,
+    /// otherwise it is centered as in ssqueezepy's `p2up`.
+    #[inline]
+    pub(crate) fn pad_left(&self, length: usize) -> usize {
+        if self.padding == PaddingMode::None {
+            return 0;
+        }
+        let right = (self.execution_length - length) / 2;
+        self.execution_length - length - right
+    }
+
+    /// Extends the signal to `execution_length` according to the padding mode.
+    pub(crate) fn pad<V: Copy + Zero>(&self, input: &[V]) -> Result<Vec<V>, ScaletError> {
+        self.check_length(input.len())?;
+        let mut padded = try_vec![V::zero(); self.execution_length];
+        pad_signal(input, &mut padded, self.pad_left(input.len()), self.padding);
+        Ok(padded)
+    }
+
+    /// View of the original signal region of `length` samples inside a padded row.
+    #[inline]
+    pub(crate) fn crop<'a, V>(&self, row: &'a [V], length: usize) -> &'a [V] {
+        let pad_left = self.pad_left(length);
+        &row[pad_left..pad_left + length]
+    }
+
+    /// Reconstructs the frequency content of the analyzed signal the wavelet captures.
+    ///
+    /// Both formulas recover `x₊(b) = 1/C ∫ W(a, b) da / a` (the double integral after
+    /// convolving every scale with the wavelet once more), where `C` is the matching
+    /// admissibility constant. The DC component is not captured by the CWT and is lost.
+    ///
+    /// The double integral normalizes negative frequencies by the constant of the negative
+    /// half-axis, so with a non-analytic wavelet it recovers the full signal. The single
+    /// integral cannot tell the two halves apart and requires an analytic wavelet.
+    fn inverse_impl(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<Complex<T>>, ScaletError> {
+        let scales = self.view_scales();
+        if coeffs.len() != scales.len() {
+            return Err(ScaletError::InvalidInputSize(scales.len(), coeffs.len()));
+        }
+        let length = coeffs.first().map(|x| x.len()).unwrap_or(0);
+        self.check_length(length)?;
+        if let Some(row) = coeffs.iter().find(|x| x.len() != length) {
+            return Err(ScaletError::InvalidInputSize(length, row.len()));
+        }
+
+        // d(ln a) quadrature of the scale integral.
+        let weights = log_scale_weights(scales)?;
+
+        let mut result = try_vec![Complex::<T>::zero(); length];
+
+        match formula {
+            ReconstructionFormula::SingleIntegral => {
+                if !self.wavelet.is_analytic() {
+                    return Err(ScaletError::Generic(
+                        "Single-integral reconstruction requires an analytic wavelet".to_string(),
+                    ));
+                }
+                let c = self.single_integral_constant()?;
+                for ((row, &scale), &weight) in coeffs.iter().zip(scales.iter()).zip(weights.iter())
+                {
+                    let f = weight * self.l1_amplitude(scale);
+                    for (dst, &src) in result.iter_mut().zip(row.iter()) {
+                        *dst = Complex::new(fmla(src.re, f, dst.re), fmla(src.im, f, dst.im));
+                    }
+                }
+                let norm = c.inv();
+                for dst in result.iter_mut() {
+                    *dst = *dst * norm;
+                }
+            }
+            ReconstructionFormula::DoubleIntegral => {
+                let c = self.double_integral_constant()?;
+                let c_negative = self.negative_double_integral_constant()?;
+                let mut workspace = self.make_workspace()?;
+                let workspace = self.split_workspace(&mut workspace);
+                // The padded row buffer holds the spectrum of each coefficient row.
+                let spectrum = workspace.padded_row;
+                let scratch = workspace.scratch;
+                let mut accumulator = try_vec![Complex::<T>::zero(); self.execution_length];
+
+                for (index, ((row, &scale), &weight)) in coeffs
+                    .iter()
+                    .zip(scales.iter())
+                    .zip(weights.iter())
+                    .enumerate()
+                {
+                    // Coefficients outside the signal region are unknown and taken as zeros.
+                    pad_signal(row, spectrum, self.pad_left(length), PaddingMode::Zero);
+                    self.fft_forward
+                        .execute_with_scratch(spectrum, scratch)
+                        .map_err(|x| ScaletError::FftError(x.to_string()))?;
+
+                    let wavelet_fft =
+                        self.scale_filter(index, workspace.current_psi, workspace.filter)?;
+
+                    // Convolve with the wavelet itself (no conjugate) and accumulate,
+                    // linearity of the IFFT lets all scales share a single inverse.
+                    let f = weight * self.l1_amplitude(scale);
+                    for ((dst, &src), &psi) in accumulator
+                        .iter_mut()
+                        .zip(spectrum.iter())
+                        .zip(wavelet_fft.iter())
+                    {
+                        let v = src * psi;
+                        *dst = Complex::new(fmla(v.re, f, dst.re), fmla(v.im, f, dst.im));
+                    }
+                }
 
-            // --- Step 3: Inverse Transform to the Time Domain ---
+                // Each half of the spectrum is scaled by the constant of its own half-axis,
+                // negative frequencies a wavelet does not capture stay zero.
+                let n: T = self.execution_length.as_();
+                let norm = 1.0f64.as_() / (c * n);
+                let norm_negative = if c_negative > T::zero() {
+                    1.0f64.as_() / (c_negative * n)
+                } else {
+                    T::zero()
+                };
+                for (dst, &w) in accumulator.iter_mut().zip(self.psi.iter()) {
+                    *dst = *dst * if w < T::zero() { norm_negative } else { norm };
+                }
 
-            // Perform the Inverse FFT (IFFT) to transform the resulting spectrum back to the time domain.
-            // The result in v_dst is the complex CWT coefficients Wx(a, b) at the current scale 'a'.
-            self.fft_inverse
-                .execute_with_scratch(v_dst, &mut scratch)
-                .map_err(|x| ScaletError::FftError(x.to_string()))?;
+                self.fft_inverse
+                    .execute_with_scratch(&mut accumulator, scratch)
+                    .map_err(|x| ScaletError::FftError(x.to_string()))?;
+
+                result.copy_from_slice(self.crop(&accumulator, length));
+            }
         }
 
         Ok(result)
@@ -143,37 +836,610 @@ where
     usize: AsPrimitive<T>,
 {
     fn execute(&self, input: &[T]) -> Result<Vec<Vec<Complex<T>>>, ScaletError> {
-        if self.execution_length != input.len() {
-            return Err(ScaletError::InvalidInputSize(
-                self.execution_length,
-                input.len(),
-            ));
-        }
-
-        let mut signal_fft: Vec<Complex<T>> = try_vec![Complex::<T>::default(); input.len()];
-        for (dst, &src) in signal_fft.iter_mut().zip(input.iter()) {
-            *dst = Complex::new(src, Zero::zero());
-        }
-        self.execute_impl(&mut signal_fft)
+        let padded = self.pad(input)?;
+        let mut scratch = try_vec![Complex::zero(); self.scratch_length];
+        // 1. Transform the input signal into the frequency domain (Spectral Domain).
+        let mut signal_fft = try_vec![Complex::zero(); self.real_spectrum_length()];
+        self.real_spectrum(&padded, &mut signal_fft, &mut scratch)?;
+        self.execute_impl(&signal_fft, input.len())
     }
 
     fn execute_complex(&self, input: &[Complex<T>]) -> Result<Vec<Vec<Complex<T>>>, ScaletError> {
-        if self.execution_length != input.len() {
-            return Err(ScaletError::InvalidInputSize(
-                self.execution_length,
-                input.len(),
-            ));
-        }
+        let mut signal_fft = self.pad(input)?;
+        let mut scratch = try_vec![Complex::zero(); self.scratch_length];
+        // 1. Transform the input signal into the frequency domain (Spectral Domain).
+        self.forward_spectrum(&mut signal_fft, &mut scratch)?;
+        self.execute_impl(&signal_fft, input.len())
+    }
 
-        let mut signal_fft = input.to_vec();
-        self.execute_impl(&mut signal_fft)
+    fn execute_into(
+        &self,
+        input: &[T],
+        out: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), ScaletError> {
+        self.check_length(input.len())?;
+        self.check_output(input.len(), out)?;
+        let (spectrum, padded, mut workspace) = self.split_scratch(scratch)?;
+        pad_signal(input, padded, self.pad_left(input.len()), self.padding);
+        let spectrum = &mut spectrum[..self.real_spectrum_length()];
+        self.real_spectrum(padded, spectrum, workspace.scratch)?;
+        self.execute_into_impl(spectrum, input.len(), out, &mut workspace)
+    }
+
+    fn execute_complex_into(
+        &self,
+        input: &[Complex<T>],
+        out: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), ScaletError> {
+        self.check_length(input.len())?;
+        self.check_output(input.len(), out)?;
+        let (spectrum, _, mut workspace) = self.split_scratch(scratch)?;
+        pad_signal(input, spectrum, self.pad_left(input.len()), self.padding);
+        self.forward_spectrum(spectrum, workspace.scratch)?;
+        self.execute_into_impl(spectrum, input.len(), out, &mut workspace)
+    }
+
+    fn execute_batch(&self, channels: &[&[T]]) -> Result<Vec<Vec<Vec<Complex<T>>>>, ScaletError> {
+        let length = self.check_batch(channels)?;
+        let mut result = try_vec![try_vec![try_vec![Complex::zero(); length]; self.scales.len()]; channels.len()];
+        self.execute_batch_blocks(
+            channels,
+            length,
+            &mut result,
+            1,
+            |dst, channel, scale, row| dst[channel][scale].copy_from_slice(row),
+        )?;
+        Ok(result)
+    }
+
+    fn execute_batch_flat(&self, channels: &[&[T]]) -> Result<Vec<Complex<T>>, ScaletError> {
+        let length = self.check_batch(channels)?;
+        let scales = self.scales.len();
+        let mut result = try_vec![Complex::zero(); channels.len() * scales * length];
+        self.execute_batch_blocks(
+            channels,
+            length,
+            &mut result,
+            scales * length,
+            |dst, channel, scale, row| {
+                let offset = (channel * scales + scale) * length;
+                dst[offset..offset + length].copy_from_slice(row)
+            },
+        )?;
+        Ok(result)
+    }
+
+    fn execute_power(&self, input: &[T]) -> Result<Vec<Vec<T>>, ScaletError> {
+        self.execute_real(input, RealOutput::Power)
+    }
+
+    fn execute_magnitude(&self, input: &[T]) -> Result<Vec<Vec<T>>, ScaletError> {
+        self.execute_real(input, RealOutput::Magnitude)
+    }
+
+    fn execute_power_into(
+        &self,
+        input: &[T],
+        out: &mut [T],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), ScaletError> {
+        self.execute_real_into_impl(input, out, scratch, RealOutput::Power)
+    }
+
+    fn execute_magnitude_into(
+        &self,
+        input: &[T],
+        out: &mut [T],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), ScaletError> {
+        self.execute_real_into_impl(input, out, scratch, RealOutput::Magnitude)
+    }
+
+    fn scratch_length(&self) -> usize {
+        self.execution_length + self.execution_length.div_ceil(2) + self.workspace_length()
+    }
+
+    fn inverse(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<T>, ScaletError> {
+        let restored = self.inverse_impl(coeffs, formula)?;
+        // A real signal is twice the real part of its positive-frequency half, unless
+        // the wavelet captured the negative half as well and the signal is restored whole.
+        let factor: T = if formula == ReconstructionFormula::DoubleIntegral
+            && self.negative_double_integral_constant()? > T::zero()
+        {
+            1.0f64.as_()
+        } else {
+            2.0f64.as_()
+        };
+        Ok(restored.iter().map(|x| x.re * factor).collect())
+    }
+
+    fn inverse_complex(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<Complex<T>>, ScaletError> {
+        self.inverse_impl(coeffs, formula)
     }
 
     fn length(&self) -> usize {
-        self.execution_length
+        self.length
     }
 
     fn view_scales(&self) -> &[T] {
         &self.scales
     }
+
+    fn filter_bank_size(&self) -> usize {
+        self.filter_bank_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CmhatWavelet, CwtOptions, CwtWavelet, GaborWavelet, HhhatWavelet, MorletWavelet,
+        PaddingMode, ReconstructionFormula, Scalet, ScaletError,
+    };
+    use num_complex::Complex;
+    use std::f64::consts::PI;
+    use std::sync::Arc;
+
+    /// Tones that are periodic over `n` samples and lie inside the analyzed band.
+    fn periodic_signal(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| {
+                let w = 2. * PI * i as f64 / n as f64;
+                (33. * w).sin() + 0.3 * (260. * w).cos() + 0.2 * (9. * w).sin()
+            })
+            .collect()
+    }
+
+    fn relative_error(actual: &[f64], expected: &[f64]) -> f64 {
+        let error = actual
+            .iter()
+            .zip(expected.iter())
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f64>();
+        let energy = expected.iter().map(|x| x * x).sum::<f64>();
+        (error / energy).sqrt()
+    }
+
+    #[test]
+    fn inverse_roundtrip_f64() -> Result<(), ScaletError> {
+        let n = 2048;
+        let signal = periodic_signal(n);
+        for l1_norm in [true, false] {
+            let options = CwtOptions {
+                l1_norm,
+                ..Default::default()
+            };
+            let cwt = Scalet::make_cwt_f64(Arc::new(MorletWavelet::default()), n, options)?;
+            let coeffs = cwt.execute(&signal)?;
+            let single = cwt.inverse(&coeffs, ReconstructionFormula::SingleIntegral)?;
+            assert!(relative_error(&single, &signal) < 1e-2);
+            let double = cwt.inverse(&coeffs, ReconstructionFormula::DoubleIntegral)?;
+            assert!(relative_error(&double, &signal) < 1e-3);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn inverse_roundtrip_f32() -> Result<(), ScaletError> {
+        let n = 2048;
+        let signal = periodic_signal(n);
+        let input = signal.iter().map(|&x| x as f32).collect::<Vec<f32>>();
+        let cwt =
+            Scalet::make_cwt_f32(Arc::new(MorletWavelet::default()), n, CwtOptions::default())?;
+        let coeffs = cwt.execute(&input)?;
+        for formula in [
+            ReconstructionFormula::SingleIntegral,
+            ReconstructionFormula::DoubleIntegral,
+        ] {
+            let restored = cwt.inverse(&coeffs, formula)?;
+            let restored = restored.iter().map(|&x| x as f64).collect::<Vec<f64>>();
+            assert!(relative_error(&restored, &signal) < 1e-2);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn low_mu_morlet_single_integral_roundtrip_f64() -> Result<(), ScaletError> {
+        // mu = 6 leaks exp(-18) of its peak into negative frequencies, which is above
+        // f64 epsilon but still counts as analytic, so the single integral applies.
+        let n = 2048;
+        let signal = periodic_signal(n);
+        let cwt = Scalet::make_cwt_f64(Arc::new(MorletWavelet::new(6.)), n, CwtOptions::default())?;
+        let coeffs = cwt.execute(&signal)?;
+        let single = cwt.inverse(&coeffs, ReconstructionFormula::SingleIntegral)?;
+        assert!(relative_error(&single, &signal) < 1e-2);
+        Ok(())
+    }
+
+    #[test]
+    fn inverse_complex_restores_half_the_analytic_signal() -> Result<(), ScaletError> {
+        let n = 1024;
+        let signal = periodic_signal(n);
+        let input = signal
+            .iter()
+            .map(|&x| Complex::new(x, 0.))
+            .collect::<Vec<_>>();
+        let cwt =
+            Scalet::make_cwt_f64(Arc::new(MorletWavelet::default()), n, CwtOptions::default())?;
+        let coeffs = cwt.execute_complex(&input)?;
+        let restored = cwt.inverse_complex(&coeffs, ReconstructionFormula::DoubleIntegral)?;
+        let real = restored.iter().map(|x| 2. * x.re).collect::<Vec<f64>>();
+        assert!(relative_error(&real, &signal) < 1e-3);
+        // The real-valued inverse is the real part of the complex one, doubled.
+        let real_inverse = cwt.inverse(
+            &cwt.execute(&signal)?,
+            ReconstructionFormula::DoubleIntegral,
+        )?;
+        assert!(relative_error(&real, &real_inverse) < 1e-9);
+        Ok(())
+    }
+
+    /// Real and even in frequency, so it responds to both half-axes alike.
+    struct MexicanHat;
+
+    impl CwtWavelet<f64> for MexicanHat {
+        fn make_wavelet(&self, omegas: &[f64]) -> Result<Vec<Complex<f64>>, ScaletError> {
+            Ok(omegas
+                .iter()
+                .map(|&w| Complex::new(w * w * (-0.5 * w * w).exp(), 0.))
+                .collect())
+        }
+    }
+
+    #[test]
+    fn inverse_roundtrip_non_analytic_wavelet() -> Result<(), ScaletError> {
+        let n = 2048;
+        let signal = periodic_signal(n);
+        // A low center frequency leaves a significant response at negative frequencies.
+        let cwt = Scalet::make_cwt_f64(Arc::new(MexicanHat), n, CwtOptions::default())?;
+        let coeffs = cwt.execute(&signal)?;
+        let restored = cwt.inverse(&coeffs, ReconstructionFormula::DoubleIntegral)?;
+        assert!(relative_error(&restored, &signal) < 1e-3);
+        assert!(
+            cwt.inverse(&coeffs, ReconstructionFormula::SingleIntegral)
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn inverse_rejects_mismatched_coefficients() -> Result<(), ScaletError> {
+        let n = 256;
+        let cwt =
+            Scalet::make_cwt_f64(Arc::new(MorletWavelet::default()), n, CwtOptions::default())?;
+        let mut coeffs = cwt.execute(&periodic_signal(n))?;
+        coeffs.pop();
+        assert!(
+            cwt.inverse(&coeffs, ReconstructionFormula::SingleIntegral)
+                .is_err()
+        );
+        let mut coeffs = cwt.execute(&periodic_signal(n))?;
+        coeffs[1].pop();
+        assert!(
+            cwt.inverse(&coeffs, ReconstructionFormula::DoubleIntegral)
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn padding_controls_the_boundary_response() -> Result<(), ScaletError> {
+        // A constant has no wavelet content, only the extension creates a response.
+        let n = 300;
+        let signal = vec![1.; n];
+        let peak = |padding| -> Result<f64, ScaletError> {
+            let cwt = Scalet::make_cwt_f64(
+                Arc::new(MorletWavelet::default()),
+                n,
+                CwtOptions {
+                    padding,
+                    ..Default::default()
+                },
+            )?;
+            Ok(cwt
+                .execute(&signal)?
+                .iter()
+                .flatten()
+                .fold(0., |acc: f64, x| acc.max(x.norm())))
+        };
+        for padding in [
+            PaddingMode::Reflect,
+            PaddingMode::Symmetric,
+            PaddingMode::Periodic,
+            PaddingMode::Edge,
+        ] {
+            assert!(peak(padding)? < 1e-6);
+        }
+        assert!(peak(PaddingMode::Zero)? > 1e-2);
+        Ok(())
+    }
+
+    #[test]
+    fn prime_and_short_inputs_are_zero_extended() -> Result<(), ScaletError> {
+        // 1009 is prime and runs at the FFT length 1024, as an executor for 1024 samples does.
+        let prime = 1009;
+        let signal = periodic_signal(1024);
+        let cwt = Scalet::make_cwt_f64(
+            Arc::new(MorletWavelet::default()),
+            prime,
+            CwtOptions::default(),
+        )?;
+        let fast = Scalet::make_cwt_f64(
+            Arc::new(MorletWavelet::default()),
+            1024,
+            CwtOptions::default(),
+        )?;
+        assert_eq!(cwt.view_scales(), fast.view_scales());
+        for length in [prime, 600, 1] {
+            let mut extended = vec![0.; 1024];
+            extended[..length].copy_from_slice(&signal[..length]);
+            let rows = cwt.execute(&signal[..length])?;
+            let expected = fast.execute(&extended)?;
+            for (row, expected) in rows.iter().zip(expected.iter()) {
+                assert_eq!(row.as_slice(), &expected[..length]);
+            }
+        }
+        assert!(cwt.execute(&signal[..prime + 1]).is_err());
+        assert!(cwt.execute(&[]).is_err());
+
+        let padded = Scalet::make_cwt_f64(
+            Arc::new(MorletWavelet::default()),
+            prime,
+            CwtOptions {
+                padding: PaddingMode::Reflect,
+                ..Default::default()
+            },
+        )?;
+        for length in [prime, 321] {
+            let rows = padded.execute(&signal[..length])?;
+            assert!(rows.iter().all(|row| row.len() == length));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn filter_bank_matches_on_the_fly_filters() -> Result<(), ScaletError> {
+        let n = 700;
+        let signal = periodic_signal(n);
+        let wavelets: [Arc<dyn CwtWavelet<f64> + Send + Sync>; 2] =
+            [Arc::new(MorletWavelet::default()), Arc::new(MexicanHat)];
+        for wavelet in wavelets {
+            let on_the_fly = Scalet::make_cwt_f64(wavelet.clone(), n, CwtOptions::default())?;
+            let precomputed = Scalet::make_cwt_f64(
+                wavelet,
+                n,
+                CwtOptions {
+                    precompute_filters: true,
+                    ..Default::default()
+                },
+            )?;
+            assert_eq!(precomputed.execute(&signal)?, on_the_fly.execute(&signal)?);
+            assert_eq!(
+                precomputed.execute_power(&signal[..500])?,
+                on_the_fly.execute_power(&signal[..500])?
+            );
+            let coeffs = on_the_fly.execute(&signal)?;
+            assert_eq!(
+                precomputed.inverse(&coeffs, ReconstructionFormula::DoubleIntegral)?,
+                on_the_fly.inverse(&coeffs, ReconstructionFormula::DoubleIntegral)?
+            );
+            assert_eq!(
+                precomputed.filter_bank_size(),
+                on_the_fly.filter_bank_size()
+            );
+            assert!(precomputed.filter_bank_size() > 0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn real_fft_path_matches_complex_execution() -> Result<(), ScaletError> {
+        let n = 900;
+        let signal = periodic_signal(n);
+        let complex = signal
+            .iter()
+            .map(|&x| Complex::new(x, 0.))
+            .collect::<Vec<_>>();
+        let wavelets: [Arc<dyn CwtWavelet<f64> + Send + Sync>; 4] = [
+            Arc::new(MorletWavelet::default()),
+            Arc::new(GaborWavelet::default()),
+            Arc::new(CmhatWavelet::default()),
+            Arc::new(HhhatWavelet::default()),
+        ];
+        for wavelet in wavelets {
+            // Every bundled wavelet takes the real-to-complex path.
+            assert!(wavelet.is_analytic());
+            let cwt = Scalet::make_cwt_f64(wavelet, n, CwtOptions::default())?;
+            let real = cwt.execute(&signal)?;
+            let expected = cwt.execute_complex(&complex)?;
+            let peak = expected
+                .iter()
+                .flatten()
+                .fold(0f64, |acc, x| acc.max(x.norm()));
+            for (row, expected) in real.iter().zip(expected.iter()) {
+                for (a, b) in row.iter().zip(expected.iter()) {
+                    assert!((a - b).norm() <= 1e-12 * peak);
+                }
+            }
+        }
+        assert!(!GaborWavelet::new(1., 2., 0.).is_analytic());
+        Ok(())
+    }
+
+    #[test]
+    fn power_and_magnitude_match_coefficients() -> Result<(), ScaletError> {
+        // Odd lengths exercise the remainders of the vectorized loops.
+        let n = 999;
+        let signal = periodic_signal(n);
+        let cwt =
+            Scalet::make_cwt_f64(Arc::new(MorletWavelet::default()), n, CwtOptions::default())?;
+        let coeffs = cwt.execute(&signal)?;
+        let power = cwt.execute_power(&signal)?;
+        let magnitude = cwt.execute_magnitude(&signal)?;
+        for ((row, power), magnitude) in coeffs.iter().zip(power.iter()).zip(magnitude.iter()) {
+            for ((w, &p), &m) in row.iter().zip(power.iter()).zip(magnitude.iter()) {
+                assert!((p - w.norm_sqr()).abs() <= 1e-12 * w.norm_sqr().max(1e-20));
+                assert!((m - w.norm()).abs() <= 1e-12 * w.norm().max(1e-10));
+            }
+        }
+
+        let input = signal.iter().map(|&x| x as f32).collect::<Vec<f32>>();
+        let cwt =
+            Scalet::make_cwt_f32(Arc::new(MorletWavelet::default()), n, CwtOptions::default())?;
+        let coeffs = cwt.execute(&input)?;
+        let peak = coeffs
+            .iter()
+            .flatten()
+            .fold(0f32, |acc, x| acc.max(x.norm()));
+        let power = cwt.execute_power(&input)?;
+        let magnitude = cwt.execute_magnitude(&input)?;
+        for ((row, power), magnitude) in coeffs.iter().zip(power.iter()).zip(magnitude.iter()) {
+            for ((w, &p), &m) in row.iter().zip(power.iter()).zip(magnitude.iter()) {
+                assert!((p - w.norm_sqr()).abs() <= 1e-5 * peak * peak);
+                assert!((m - w.norm()).abs() <= 1e-5 * peak);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn threaded_execution_matches_single_thread() -> Result<(), ScaletError> {
+        let n = 500;
+        let signal = periodic_signal(n);
+        let single =
+            Scalet::make_cwt_f64(Arc::new(MorletWavelet::default()), n, CwtOptions::default())?;
+        for thread_count in [2, 7] {
+            let threaded = Scalet::make_cwt_f64(
+                Arc::new(MorletWavelet::default()),
+                n,
+                CwtOptions {
+                    thread_count,
+                    ..Default::default()
+                },
+            )?;
+            assert_eq!(threaded.execute(&signal)?, single.execute(&signal)?);
+            assert_eq!(
+                threaded.execute_power(&signal)?,
+                single.execute_power(&signal)?
+            );
+            assert_eq!(
+                threaded.execute_magnitude(&signal)?,
+                single.execute_magnitude(&signal)?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn execute_into_matches_execute() -> Result<(), ScaletError> {
+        let n = 600;
+        let signal = periodic_signal(n);
+        for padding in [PaddingMode::None, PaddingMode::Reflect] {
+            let cwt = Scalet::make_cwt_f64(
+                Arc::new(MorletWavelet::default()),
+                n,
+                CwtOptions {
+                    padding,
+                    ..Default::default()
+                },
+            )?;
+            let scales = cwt.view_scales().len();
+            let mut scratch = vec![Complex::default(); cwt.scratch_length()];
+            // Inputs shorter than the executor length are accepted as well.
+            for length in [n, n - 77] {
+                let input = &signal[..length];
+                let mut out = vec![Complex::default(); scales * length];
+                cwt.execute_into(input, &mut out, &mut scratch)?;
+                let rows = cwt.execute(input)?;
+                assert_eq!(out, rows.concat());
+
+                let complex_input = input
+                    .iter()
+                    .map(|&x| Complex::new(x, 0.5 * x))
+                    .collect::<Vec<_>>();
+                cwt.execute_complex_into(&complex_input, &mut out, &mut scratch)?;
+                assert_eq!(out, cwt.execute_complex(&complex_input)?.concat());
+
+                let mut power = vec![0.; scales * length];
+                cwt.execute_power_into(input, &mut power, &mut scratch)?;
+                assert_eq!(power, cwt.execute_power(input)?.concat());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn execute_into_rejects_unexpected_buffers() -> Result<(), ScaletError> {
+        let n = 256;
+        let signal = periodic_signal(n);
+        let cwt =
+            Scalet::make_cwt_f64(Arc::new(MorletWavelet::default()), n, CwtOptions::default())?;
+        let scales = cwt.view_scales().len();
+        let mut scratch = vec![Complex::default(); cwt.scratch_length()];
+        let mut out = vec![Complex::default(); scales * n - 1];
+        assert!(cwt.execute_into(&signal, &mut out, &mut scratch).is_err());
+        let mut out = vec![Complex::default(); scales * n];
+        let mut small = vec![Complex::default(); cwt.scratch_length() - 1];
+        assert!(cwt.execute_into(&signal, &mut out, &mut small).is_err());
+        let long = periodic_signal(n + 1);
+        let mut out = vec![Complex::default(); scales * (n + 1)];
+        assert!(cwt.execute_into(&long, &mut out, &mut scratch).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn batch_matches_per_channel_execution() -> Result<(), ScaletError> {
+        let n = 400;
+        let channels = (0..5)
+            .map(|k| {
+                periodic_signal(n)
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &x)| x * (k + 1) as f64 + (0.01 * (i * k) as f64).sin())
+                    .collect::<Vec<f64>>()
+            })
+            .collect::<Vec<_>>();
+        let views = channels.iter().map(|x| x.as_slice()).collect::<Vec<_>>();
+        for (thread_count, precompute_filters) in [(1, false), (3, false), (2, true)] {
+            let cwt = Scalet::make_cwt_f64(
+                Arc::new(MorletWavelet::default()),
+                n,
+                CwtOptions {
+                    thread_count,
+                    precompute_filters,
+                    ..Default::default()
+                },
+            )?;
+            let batch = cwt.execute_batch(&views)?;
+            assert_eq!(batch.len(), channels.len());
+            for (rows, channel) in batch.iter().zip(channels.iter()) {
+                assert_eq!(rows, &cwt.execute(channel)?);
+            }
+            assert_eq!(cwt.execute_batch_flat(&views)?, batch.concat().concat());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn batch_rejects_invalid_channels() -> Result<(), ScaletError> {
+        let n = 128;
+        let cwt =
+            Scalet::make_cwt_f64(Arc::new(MorletWavelet::default()), n, CwtOptions::default())?;
+        let signal = periodic_signal(n);
+        assert!(cwt.execute_batch(&[]).is_err());
+        assert!(cwt.execute_batch(&[&signal, &signal[1..]]).is_err());
+        let long = periodic_signal(n + 1);
+        assert!(cwt.execute_batch_flat(&[&long]).is_err());
+        Ok(())
+    }
 }
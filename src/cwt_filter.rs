@@ -29,6 +29,13 @@
 use crate::err::ScaletError;
 use num_complex::Complex;
 
+/// Largest negative-frequency response, relative to the peak, that a bundled wavelet
+/// may have and still report itself as analytic.
+///
+/// The bound is fixed rather than tied to the sample precision, so a wavelet is analytic
+/// in both `f32` and `f64` or in neither.
+pub(crate) const ANALYTIC_LEAKAGE: f64 = 1e-5;
+
 /// A trait defining the required behavior for any wavelet used in the Continuous Wavelet Transform (CWT)
 /// executor.
 pub trait CwtWavelet<T> {
@@ -42,4 +49,31 @@ pub trait CwtWavelet<T> {
     ///
     /// * `omegas`: A slice of angular frequencies (omega_k) corresponding to the FFT bins.
     fn make_wavelet(&self, omegas: &[T]) -> Result<Vec<Complex<T>>, ScaletError>;
+
+    /// Generates the Fourier Transform of the wavelet into `dst` without allocating.
+    ///
+    /// `dst` has the same length as `omegas`. The default implementation delegates to
+    /// [`make_wavelet`](Self::make_wavelet) and copies, bundled wavelets write in place.
+    fn make_wavelet_into(&self, omegas: &[T], dst: &mut [Complex<T>]) -> Result<(), ScaletError>
+    where
+        T: Copy,
+    {
+        let wavelet = self.make_wavelet(omegas)?;
+        if wavelet.len() != dst.len() {
+            return Err(ScaletError::WaveletInvalidSize(dst.len(), wavelet.len()));
+        }
+        dst.copy_from_slice(&wavelet);
+        Ok(())
+    }
+
+    /// Whether the wavelet is analytic, i.e. its Fourier transform vanishes
+    /// at all negative frequencies.
+    ///
+    /// Executors use this to evaluate and multiply only the non-negative half of the spectrum,
+    /// and to transform real input with a real-to-complex FFT. Return `true` only if the
+    /// negative-frequency response is negligible, the bundled wavelets allow at most `1e-5`
+    /// of the peak magnitude. Defaults to `false`.
+    fn is_analytic(&self) -> bool {
+        false
+    }
 }
@@ -0,0 +1,382 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::cwt_executor::CommonCwtExecutor;
+use crate::err::try_vec;
+use crate::sample::CwtSample;
+use crate::{CwtAnalysis, CwtExecutor, ReconstructionFormula, ScaletError};
+use num_complex::Complex;
+use num_traits::AsPrimitive;
+use std::cmp::Ordering;
+
+/// Shrinkage applied to coefficients whose magnitude `|W|` is compared with a threshold `λ`.
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Thresholding {
+    /// Keeps coefficients above `λ` unchanged and zeroes the rest.
+    Hard,
+    /// Shrinks magnitudes by `λ`, `W · max(0, 1 - λ / |W|)`.
+    #[default]
+    Soft,
+    /// Non-negative garrote, `W · max(0, 1 - λ² / |W|²)`, between hard and soft.
+    Garrote,
+}
+
+/// How the noise level of every scale is estimated.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum NoiseEstimate {
+    /// Median absolute deviation of the given number of finest scales, where white noise
+    /// dominates, carried over to the other scales through their white-noise response.
+    FinestScales(usize),
+    /// Median absolute deviation of every scale on its own, which also follows colored noise
+    /// but overestimates the noise where a scale is dense with signal.
+    PerScale,
+    /// White noise of the given standard deviation in the input, carried over to every scale
+    /// through its white-noise response.
+    Known(f64),
+}
+
+impl Default for NoiseEstimate {
+    fn default() -> Self {
+        NoiseEstimate::FinestScales(1)
+    }
+}
+
+/// Threshold `λ` of every scale.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum DenoiseThreshold<'a, T> {
+    /// Universal threshold `σ · sqrt(2 ln n)` for `n` samples, `σ` being the noise level
+    /// of the scale.
+    #[default]
+    Universal,
+    /// `k · σ` with the given `k`.
+    Scaled(f64),
+    /// Thresholds given directly, one per scale, in units of coefficient magnitude,
+    /// every one finite and non-negative.
+    PerScale(&'a [T]),
+}
+
+/// Configuration of [`CwtAnalysis::denoise`](crate::CwtAnalysis::denoise).
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct DenoiseOptions<'a, T> {
+    /// Shrinkage rule. Default [`Thresholding::Soft`].
+    pub thresholding: Thresholding,
+    /// Noise level estimate. Default MAD of the finest scale.
+    pub noise: NoiseEstimate,
+    /// Threshold of every scale. Default [`DenoiseThreshold::Universal`].
+    pub threshold: DenoiseThreshold<'a, T>,
+    /// Zeroes the coefficients inside the cone of influence, keeping only the region
+    /// free of edge effects, see [`CwtAnalysis::cone_of_influence_mask`](crate::CwtAnalysis::cone_of_influence_mask).
+    pub exclude_coi: bool,
+    /// Inversion formula of the reconstruction.
+    pub formula: ReconstructionFormula,
+}
+
+/// Median of `values`, reordering them.
+fn median<T: CwtSample>(values: &mut [T]) -> T {
+    let middle = values.len() / 2;
+    let (_, &mut m, _) =
+        values.select_nth_unstable_by(middle, |a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    m
+}
+
+/// Standard deviation of the real and imaginary parts of `row` from their pooled
+/// median absolute deviation, `MAD / 0.6745` for Gaussian noise.
+fn mad_sigma<T: CwtSample>(row: &[Complex<T>], buffer: &mut Vec<T>) -> Result<T, ScaletError>
+where
+    f64: AsPrimitive<T>,
+{
+    buffer.clear();
+    buffer
+        .try_reserve(row.len() * 2)
+        .map_err(|_| ScaletError::Allocation(row.len() * 2))?;
+    buffer.extend(row.iter().flat_map(|w| [w.re, w.im]));
+    let center = median(buffer);
+    for v in buffer.iter_mut() {
+        *v = (*v - center).abs();
+    }
+    Ok(median(buffer) / 0.6744897501960817f64.as_())
+}
+
+impl<T: CwtSample> CommonCwtExecutor<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    pub(crate) fn noise_levels_impl(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        noise: NoiseEstimate,
+    ) -> Result<Vec<T>, ScaletError> {
+        self.check_coeffs(coeffs)?;
+        let mut buffer = Vec::new();
+        // Expected power of every scale for white noise of unit variance,
+        // and the variance of the input it is scaled by.
+        let (background, input_variance): (Vec<T>, T) = match noise {
+            NoiseEstimate::PerScale => {
+                let mut levels = try_vec![T::zero(); coeffs.len()];
+                for (dst, row) in levels.iter_mut().zip(coeffs.iter()) {
+                    *dst = mad_sigma(row, &mut buffer)?;
+                }
+                return Ok(levels);
+            }
+            NoiseEstimate::Known(sigma) => {
+                if !(0. ..f64::INFINITY).contains(&sigma) {
+                    return Err(ScaletError::Generic(format!(
+                        "Noise level must be finite and non-negative, but it was {sigma}"
+                    )));
+                }
+                (self.background_power(T::zero())?, (sigma * sigma).as_())
+            }
+            NoiseEstimate::FinestScales(count) => {
+                if count == 0 || count > self.scales.len() {
+                    return Err(ScaletError::Generic(format!(
+                        "Noise must be estimated from 1 to {} scales, but it was {count}",
+                        self.scales.len()
+                    )));
+                }
+                let background = self.background_power(T::zero())?;
+                let mut order = (0..self.scales.len()).collect::<Vec<usize>>();
+                order.sort_by(|&a, &b| {
+                    self.scales[a]
+                        .partial_cmp(&self.scales[b])
+                        .unwrap_or(Ordering::Equal)
+                });
+                // Every finest scale gives an estimate of the input variance,
+                // E|W|² = 2σ² for circular complex Gaussian coefficients.
+                let mut estimates = try_vec![T::zero(); count];
+                for (dst, &index) in estimates.iter_mut().zip(order.iter()) {
+                    let sigma = mad_sigma(&coeffs[index], &mut buffer)?;
+                    if background[index] > T::zero() {
+                        *dst = 2f64.as_() * sigma * sigma / background[index];
+                    }
+                }
+                let variance = median(&mut estimates);
+                (background, variance)
+            }
+        };
+        let mut levels = try_vec![T::zero(); coeffs.len()];
+        for (dst, &power) in levels.iter_mut().zip(background.iter()) {
+            *dst = (input_variance * power * 0.5f64.as_()).sqrt();
+        }
+        Ok(levels)
+    }
+
+    pub(crate) fn denoise_impl(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        options: DenoiseOptions<'_, T>,
+    ) -> Result<Vec<T>, ScaletError> {
+        let length = self.check_coeffs(coeffs)?;
+        let thresholds = match options.threshold {
+            DenoiseThreshold::PerScale(thresholds) => {
+                if thresholds.len() != self.scales.len() {
+                    return Err(ScaletError::InvalidInputSize(
+                        self.scales.len(),
+                        thresholds.len(),
+                    ));
+                }
+                if let Some(t) = thresholds
+                    .iter()
+                    .find(|&&t| !(t >= T::zero() && t < T::INFINITY))
+                {
+                    return Err(ScaletError::Generic(format!(
+                        "Threshold must be finite and non-negative, but it was {t}"
+                    )));
+                }
+                thresholds.to_vec()
+            }
+            DenoiseThreshold::Universal | DenoiseThreshold::Scaled(_) => {
+                let factor: T = match options.threshold {
+                    DenoiseThreshold::Scaled(k) => {
+                        if !(0. ..f64::INFINITY).contains(&k) {
+                            return Err(ScaletError::Generic(format!(
+                                "Threshold factor must be finite and non-negative, but it was {k}"
+                            )));
+                        }
+                        k.as_()
+                    }
+                    _ => (2f64 * (length.max(2) as f64).ln()).sqrt().as_(),
+                };
+                let mut levels = self.noise_levels_impl(coeffs, options.noise)?;
+                for v in levels.iter_mut() {
+                    *v *= factor;
+                }
+                levels
+            }
+        };
+        let excluded = if options.exclude_coi {
+            Some(self.cone_of_influence_mask(length)?)
+        } else {
+            None
+        };
+
+        let one: T = 1f64.as_();
+        let mut denoised = try_vec![Vec::new(); coeffs.len()];
+        for (index, (dst, row)) in denoised.iter_mut().zip(coeffs.iter()).enumerate() {
+            *dst = try_vec![Complex::new(T::zero(), T::zero()); length];
+            let threshold = thresholds[index];
+            let threshold_sqr = threshold * threshold;
+            for (t, (dst, &w)) in dst.iter_mut().zip(row.iter()).enumerate() {
+                if excluded.as_ref().is_some_and(|mask| mask[index][t]) {
+                    continue;
+                }
+                let power = w.norm_sqr();
+                if power <= threshold_sqr {
+                    continue;
+                }
+                let gain = match options.thresholding {
+                    Thresholding::Hard => one,
+                    Thresholding::Soft => one - threshold / power.sqrt(),
+                    Thresholding::Garrote => one - threshold_sqr / power,
+                };
+                *dst = w * gain;
+            }
+        }
+        self.inverse(&denoised, options.formula)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CwtOptions, CwtWavelet, MorletWavelet, PaddingMode, Scalet};
+    use std::sync::Arc;
+
+    /// Gaussian noise of standard deviation `sigma` from a fixed-seed generator.
+    fn noise(n: usize, sigma: f64) -> Vec<f64> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut uniform = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        };
+        (0..n)
+            .map(|_| {
+                let (u, v) = (uniform(), uniform());
+                sigma * (-2. * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+            })
+            .collect()
+    }
+
+    fn rmse(a: &[f64], b: &[f64]) -> f64 {
+        (a.iter()
+            .zip(b.iter())
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum::<f64>()
+            / a.len() as f64)
+            .sqrt()
+    }
+
+    #[test]
+    fn denoising_lowers_the_error_of_a_noisy_tone() -> Result<(), ScaletError> {
+        let n = 2048;
+        let sigma = 0.3;
+        let clean = (0..n)
+            .map(|i| (0.05 * i as f64).sin())
+            .collect::<Vec<f64>>();
+        let noisy = clean
+            .iter()
+            .zip(noise(n, sigma).iter())
+            .map(|(a, b)| a + b)
+            .collect::<Vec<f64>>();
+        let wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync> = Arc::new(MorletWavelet::default());
+        let options = CwtOptions {
+            padding: PaddingMode::Reflect,
+            ..Default::default()
+        };
+        let executor = Scalet::make_cwt_f64(wavelet, n, options)?;
+        let coeffs = executor.execute(&noisy)?;
+        let before = rmse(&noisy, &clean);
+        // A stationary tone fills its scales, where a per-scale estimate would take it for noise.
+        for thresholding in [
+            Thresholding::Hard,
+            Thresholding::Soft,
+            Thresholding::Garrote,
+        ] {
+            for noise in [NoiseEstimate::FinestScales(1), NoiseEstimate::Known(sigma)] {
+                let denoised = executor.denoise(
+                    &coeffs,
+                    DenoiseOptions {
+                        thresholding,
+                        noise,
+                        ..Default::default()
+                    },
+                )?;
+                let after = rmse(&denoised, &clean);
+                assert!(
+                    after < 0.5 * before,
+                    "{thresholding:?}, {noise:?}: rmse {after} from {before}"
+                );
+            }
+        }
+        // The finest scale recovers the noise level of the input.
+        let known = executor.noise_levels(&coeffs, NoiseEstimate::Known(sigma))?;
+        let estimated = executor.noise_levels(&coeffs, NoiseEstimate::FinestScales(1))?;
+        for (&k, &e) in known.iter().zip(estimated.iter()) {
+            assert!((e / k - 1.).abs() < 0.05, "{e} against {k}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_thresholds_are_rejected() -> Result<(), ScaletError> {
+        let n = 256;
+        let wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync> = Arc::new(MorletWavelet::default());
+        let executor = Scalet::make_cwt_f64(wavelet, n, CwtOptions::default())?;
+        let coeffs = executor.execute(&noise(n, 1.))?;
+        let rows = coeffs.len();
+        for bad in [-1., f64::NAN, f64::INFINITY] {
+            let scaled = DenoiseOptions {
+                threshold: DenoiseThreshold::Scaled(bad),
+                ..Default::default()
+            };
+            assert!(executor.denoise(&coeffs, scaled).is_err());
+            let mut thresholds = vec![0.5; rows];
+            thresholds[rows / 2] = bad;
+            let per_scale = DenoiseOptions {
+                threshold: DenoiseThreshold::PerScale(&thresholds),
+                ..Default::default()
+            };
+            assert!(executor.denoise(&coeffs, per_scale).is_err());
+        }
+        let short = vec![0.5; rows - 1];
+        let per_scale = DenoiseOptions {
+            threshold: DenoiseThreshold::PerScale(&short),
+            ..Default::default()
+        };
+        assert!(executor.denoise(&coeffs, per_scale).is_err());
+        let zeros = vec![0.; rows];
+        let per_scale = DenoiseOptions {
+            threshold: DenoiseThreshold::PerScale(&zeros),
+            ..Default::default()
+        };
+        assert!(executor.denoise(&coeffs, per_scale).is_ok());
+        Ok(())
+    }
+}
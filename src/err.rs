@@ -30,7 +30,7 @@ use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 
 /// Errors that can occur during the detrending process.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ScaletError {
     /// Indicates a failure to allocate the memory required for the resulting vector.
     /// The associated value is the requested size (`usize`) of the allocation.
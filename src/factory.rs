@@ -28,29 +28,36 @@
  */
 use crate::cwt_executor::CommonCwtExecutor;
 use crate::err::try_vec;
+use crate::padding::{PaddingMode, fast_fft_length, padded_length};
 use crate::sample::CwtSample;
-use crate::scale_bounds::find_min_max_scales;
+use crate::scale_bounds::{ScaleBounds, find_min_max_scales};
 use crate::scales::{linear_scales, log_piecewise_scales};
-use crate::{CwtExecutor, CwtOptions, CwtWavelet, ScaleType, ScaletError};
+use crate::ssq::SsqCwtExecutor;
+use crate::stream::CwtStream;
+use crate::time_support::wavelet_half_support;
+use crate::{
+    CwtAnalysis, CwtOptions, CwtStreamExecutor, CwtStreamOptions, CwtWavelet, ScaleType,
+    ScaletError, SsqExecutor, SsqOptions,
+};
+use novtb::ThreadPool;
 use num_traits::AsPrimitive;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use zaft::FftDirection;
 
 pub(crate) fn gen_psi<T: CwtSample>(points: usize) -> Result<Vec<T>, ScaletError>
 where
     usize: AsPrimitive<T>,
     f64: AsPrimitive<T>,
-    isize: AsPrimitive<T>,
 {
     let mut psih = try_vec![T::zero(); points];
     let recip_points = 1f64.as_() / points.as_();
     for (i, v) in psih.iter_mut().enumerate() {
-        let idx = if i < points / 2 {
-            i as isize
+        let idx: T = if i < points / 2 {
+            i.as_()
         } else {
-            i as isize - points as isize
+            -(points - i).as_()
         };
-        let w = idx.as_() * T::TWO_PI * recip_points;
+        let w = idx * T::TWO_PI * recip_points;
         *v = w;
     }
     Ok(psih)
@@ -61,37 +68,407 @@ pub(crate) fn create_cwt<T: CwtSample>(
     filter_size: usize,
     scale_type: ScaleType,
     options: CwtOptions,
-) -> Result<Arc<dyn CwtExecutor<T> + Send + Sync>, ScaletError>
+) -> Result<Arc<dyn CwtAnalysis<T> + Send + Sync>, ScaletError>
 where
     usize: AsPrimitive<T>,
     f64: AsPrimitive<T>,
     isize: AsPrimitive<T>,
 {
-    if filter_size == 0 {
+    Ok(Arc::new(make_common_cwt(
+        wavelet,
+        filter_size,
+        scale_type,
+        options,
+    )?))
+}
+
+/// Builds an executor over caller-provided scales.
+pub(crate) fn create_cwt_with_scales<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    filter_size: usize,
+    scales: &[T],
+    options: CwtOptions,
+) -> Result<Arc<dyn CwtAnalysis<T> + Send + Sync>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+    isize: AsPrimitive<T>,
+{
+    if scales.is_empty() {
+        return Err(ScaletError::Generic(
+            "CWT requires at least one scale".to_string(),
+        ));
+    }
+    if let Some(scale) = scales
+        .iter()
+        .find(|&&x| !(x > T::zero() && x < T::INFINITY))
+    {
+        return Err(ScaletError::Generic(format!(
+            "Scales must be finite and positive, but one was {scale:?}"
+        )));
+    }
+    let mut owned = try_vec![T::zero(); scales.len()];
+    owned.copy_from_slice(scales);
+    Ok(Arc::new(make_cwt_with_scales(
+        wavelet,
+        owned,
+        filter_size,
+        options,
+    )?))
+}
+
+pub(crate) fn create_cwt_stream<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    options: CwtStreamOptions,
+) -> Result<Box<dyn CwtStreamExecutor<T> + Send + Sync>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+    isize: AsPrimitive<T>,
+{
+    if options.hop == 0 {
+        return Err(ScaletError::ZeroBaseSized);
+    }
+    if options.planning_length == 0 {
+        return Err(ScaletError::ZeroBaseSized);
+    }
+    // Boundaries are handled by the stream itself, blocks are never padded.
+    let cwt_options = CwtOptions {
+        padding: PaddingMode::None,
+        ..options.cwt
+    };
+    // A stream has no fixed length, its scales are planned for the configured length.
+    let scales = make_length_scales(wavelet.clone(), options.planning_length, cwt_options)?;
+    let max_scale = scales
+        .iter()
+        .fold(T::zero(), |acc, &x| if x > acc { x } else { acc });
+    let context = wavelet_half_support(wavelet.as_ref(), max_scale, options.tolerance.as_())?;
+    let block_length = fast_fft_length(options.hop + 2 * context);
+    let cwt = make_cwt_with_scales(wavelet, scales, block_length, cwt_options)?;
+    Ok(Box::new(CwtStream::new(cwt, context)?))
+}
+
+pub(crate) fn create_ssq<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    filter_size: usize,
+    options: SsqOptions,
+) -> Result<Arc<dyn SsqExecutor<T> + Send + Sync>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+    isize: AsPrimitive<T>,
+{
+    let cwt = make_common_cwt(wavelet, filter_size, options.cwt.scale_type, options.cwt)?;
+    Ok(Arc::new(SsqCwtExecutor::new(cwt, options)?))
+}
+
+fn make_common_cwt<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    filter_size: usize,
+    scale_type: ScaleType,
+    options: CwtOptions,
+) -> Result<CommonCwtExecutor<T>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+    isize: AsPrimitive<T>,
+{
+    let scales = make_length_scales(
+        wavelet.clone(),
+        filter_size,
+        CwtOptions {
+            scale_type,
+            ..options
+        },
+    )?;
+    make_cwt_with_scales(wavelet, scales, filter_size, options)
+}
+
+/// FFT length an executor runs at for signals up to `length` samples.
+pub(crate) fn execution_length(length: usize, padding: PaddingMode) -> usize {
+    fast_fft_length(padded_length(length, padding))
+}
+
+/// Scale grid between `bounds`, `nv` is interpreted according to `scale_type`.
+pub(crate) fn scale_grid<T: CwtSample>(
+    bounds: ScaleBounds<T>,
+    scale_type: ScaleType,
+    nv: usize,
+) -> Result<Vec<T>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    isize: AsPrimitive<T>,
+{
+    if !(bounds.min > T::zero() && bounds.min <= bounds.max && bounds.max < T::INFINITY) {
+        return Err(ScaletError::Generic(format!(
+            "Invalid scale bounds [{}, {}]",
+            bounds.min, bounds.max
+        )));
+    }
+    if nv == 0 {
         return Err(ScaletError::ZeroBaseSized);
     }
-    let scale_bounds = find_min_max_scales(wavelet.clone(), -0.5f64.as_())?;
     let scales = match scale_type {
-        ScaleType::Log => {
-            log_piecewise_scales(scale_bounds.min, scale_bounds.max, options.nv.as_())?
-        }
-        ScaleType::Linear => linear_scales(scale_bounds.min, scale_bounds.max, options.nv.as_())?,
+        ScaleType::Log => log_piecewise_scales(bounds.min, bounds.max, nv.as_()),
+        ScaleType::Linear => linear_scales(bounds.min, bounds.max, nv),
+    }?;
+    if scales.is_empty() {
+        return Ok(vec![bounds.min]);
+    }
+    Ok(scales)
+}
+
+/// Scale bounds of an executor for signals up to `length` samples.
+pub(crate) fn make_scale_bounds<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    length: usize,
+    options: CwtOptions,
+) -> Result<ScaleBounds<T>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    if length == 0 {
+        return Err(ScaletError::ZeroBaseSized);
+    }
+    find_min_max_scales(
+        wavelet,
+        execution_length(length, options.padding),
+        options.scale_cutoffs,
+        &options.wavelet_search,
+    )
+}
+
+/// Scales of an executor for signals up to `length` samples.
+pub(crate) fn make_length_scales<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    length: usize,
+    options: CwtOptions,
+) -> Result<Vec<T>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+    isize: AsPrimitive<T>,
+{
+    let bounds = make_scale_bounds(wavelet, length, options)?;
+    scale_grid(bounds, options.scale_type, options.nv)
+}
+
+pub(crate) fn make_cwt_with_scales<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    scales: Vec<T>,
+    filter_size: usize,
+    options: CwtOptions,
+) -> Result<CommonCwtExecutor<T>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+    isize: AsPrimitive<T>,
+{
+    if filter_size == 0 {
+        return Err(ScaletError::ZeroBaseSized);
+    }
+    let execution_length = execution_length(filter_size, options.padding);
+    let fft_forward = T::make_fft(execution_length, FftDirection::Forward)?;
+    let fft_inverse = T::make_fft(execution_length, FftDirection::Inverse)?;
+    let psi = gen_psi(execution_length)?;
+    let (fft_real, filter_length) = if wavelet.is_analytic() {
+        let fft_real = T::make_r2c_fft(execution_length)?;
+        let filter_length = fft_real.complex_length();
+        (Some(fft_real), filter_length)
+    } else {
+        (None, execution_length)
     };
-    let fft_forward = T::make_fft(filter_size, FftDirection::Forward)?;
-    let fft_inverse = T::make_fft(filter_size, FftDirection::Inverse)?;
-    let psi = gen_psi(filter_size)?;
     let scratch_length = fft_inverse
         .scratch_length()
-        .max(fft_forward.scratch_length());
-    Ok(Arc::new(CommonCwtExecutor {
+        .max(fft_forward.scratch_length())
+        .max(
+            fft_real
+                .as_ref()
+                .map(|x| x.complex_scratch_length())
+                .unwrap_or(0),
+        );
+    let mut executor = CommonCwtExecutor {
         wavelet,
         fft_forward,
         fft_inverse,
+        fft_real,
         scales,
         psi,
-        execution_length: filter_size,
+        length: filter_size,
+        execution_length,
+        padding: options.padding,
+        filter_length,
         l1_norm: options.l1_norm,
         spectrum_arithmetic: T::spectrum_arithmetic(),
         scratch_length,
-    }))
+        pool: ThreadPool::new(options.thread_count),
+        wavelet_search: options.wavelet_search,
+        single_integral_constant: OnceLock::new(),
+        double_integral_constant: OnceLock::new(),
+        negative_double_integral_constant: OnceLock::new(),
+        e_folding_ratio: OnceLock::new(),
+        filter_bank: None,
+    };
+    if options.precompute_filters {
+        executor.filter_bank = Some(executor.make_filter_bank()?);
+    }
+    Ok(executor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GaborWavelet, MorletWavelet, Scalet};
+
+    fn morlet() -> Arc<dyn CwtWavelet<f64> + Send + Sync> {
+        Arc::new(MorletWavelet::default())
+    }
+
+    #[test]
+    fn explicit_scales_are_used_as_given() -> Result<(), ScaletError> {
+        let n = 512;
+        // Unsorted and unevenly spaced on purpose, the rows follow the given order.
+        let scales = [40., 3., 5.5, 9.25, 120.];
+        let options = CwtOptions {
+            nv: 3,
+            scale_type: ScaleType::Linear,
+            ..Default::default()
+        };
+        let executor = Scalet::make_cwt_with_scales_f64(morlet(), n, &scales, options)?;
+        assert_eq!(executor.view_scales(), &scales);
+        let signal = (0..n)
+            .map(|i| (0.3 * i as f64).sin() + (0.05 * i as f64).cos())
+            .collect::<Vec<f64>>();
+        let rows = executor.execute(&signal)?;
+        assert_eq!(rows.len(), scales.len());
+        // Every row is the one an executor over that single scale produces.
+        for (row, &scale) in rows.iter().zip(scales.iter()) {
+            let single = Scalet::make_cwt_with_scales_f64(morlet(), n, &[scale], options)?;
+            assert_eq!(&single.execute(&signal)?[0], row);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_scales_are_rejected() {
+        let options = CwtOptions::default();
+        for scales in [
+            &[][..],
+            &[1., 0.],
+            &[-2.],
+            &[3., f64::NAN],
+            &[f64::INFINITY],
+        ] {
+            assert!(Scalet::make_cwt_with_scales_f64(morlet(), 256, scales, options).is_err());
+        }
+    }
+
+    #[test]
+    fn make_scales_match_the_executor() -> Result<(), ScaletError> {
+        for scale_type in [ScaleType::Log, ScaleType::Linear] {
+            for padding in [PaddingMode::None, PaddingMode::Reflect] {
+                let options = CwtOptions {
+                    scale_type,
+                    padding,
+                    ..Default::default()
+                };
+                let scales = Scalet::make_scales_f64(morlet(), 300, options)?;
+                let executor = Scalet::make_cwt_f64(morlet(), 300, options)?;
+                assert_eq!(executor.view_scales(), scales.as_slice());
+                let bounds = Scalet::scale_bounds_f64(morlet(), 300, options)?;
+                assert_eq!(scale_grid(bounds, scale_type, options.nv)?, scales);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn log_grid_steps_by_one_voice() -> Result<(), ScaletError> {
+        let bounds = ScaleBounds { min: 3., max: 200. };
+        for nv in [1, 8, 32] {
+            let scales = scale_grid(bounds, ScaleType::Log, nv)?;
+            let voice = 2f64.powf(1. / nv as f64);
+            assert!(scales[0] <= bounds.min && scales[0] * voice > bounds.min);
+            assert!(scales[scales.len() - 1] * voice * voice > bounds.max);
+            for pair in scales.windows(2) {
+                assert!((pair[1] / pair[0] - voice).abs() < 1e-12);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn linear_grid_has_nv_scales() -> Result<(), ScaletError> {
+        let bounds = ScaleBounds { min: 3., max: 200. };
+        for nv in [2, 8, 32] {
+            let scales = scale_grid(bounds, ScaleType::Linear, nv)?;
+            assert_eq!(scales.len(), nv);
+            assert_eq!(scales[0], bounds.min);
+            assert!((scales[nv - 1] - bounds.max).abs() < 1e-9);
+            let step = (bounds.max - bounds.min) / (nv - 1) as f64;
+            for pair in scales.windows(2) {
+                assert!((pair[1] - pair[0] - step).abs() < 1e-9);
+            }
+        }
+        assert_eq!(scale_grid(bounds, ScaleType::Linear, 1)?, vec![bounds.min]);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_grids_are_rejected() {
+        for bounds in [
+            ScaleBounds { min: 0., max: 10. },
+            ScaleBounds { min: 10., max: 5. },
+            ScaleBounds {
+                min: 1.,
+                max: f64::INFINITY,
+            },
+            ScaleBounds {
+                min: f64::NAN,
+                max: 10.,
+            },
+        ] {
+            assert!(scale_grid(bounds, ScaleType::Log, 8).is_err(), "{bounds:?}");
+        }
+        let bounds = ScaleBounds { min: 1., max: 10. };
+        assert!(scale_grid(bounds, ScaleType::Linear, 0).is_err());
+        assert!(Scalet::make_scales_f64(morlet(), 0, CwtOptions::default()).is_err());
+    }
+
+    #[test]
+    fn low_mu_wavelets_use_the_real_fft_in_every_precision() -> Result<(), ScaletError> {
+        let n = 512;
+        let scales = [2., 8., 32.];
+        let morlet = make_cwt_with_scales(
+            Arc::new(MorletWavelet::<f64>::new(6.)),
+            scales.to_vec(),
+            n,
+            CwtOptions::default(),
+        )?;
+        assert!(morlet.fft_real.is_some());
+        assert_eq!(morlet.filter_length, morlet.execution_length / 2 + 1);
+        let morlet = make_cwt_with_scales(
+            Arc::new(MorletWavelet::<f32>::new(6.)),
+            scales.iter().map(|&x| x as f32).collect(),
+            n,
+            CwtOptions::default(),
+        )?;
+        assert!(morlet.fft_real.is_some());
+        let gabor = make_cwt_with_scales(
+            Arc::new(GaborWavelet::<f64>::new(1., 6., 0.)),
+            scales.to_vec(),
+            n,
+            CwtOptions::default(),
+        )?;
+        assert!(gabor.fft_real.is_some());
+        let gabor = make_cwt_with_scales(
+            Arc::new(GaborWavelet::<f32>::new(1., 6., 0.)),
+            scales.iter().map(|&x| x as f32).collect(),
+            n,
+            CwtOptions::default(),
+        )?;
+        assert!(gabor.fft_real.is_some());
+        Ok(())
+    }
 }
@@ -29,29 +29,75 @@
 use crate::err::try_vec;
 use crate::factory::gen_psi;
 use crate::sample::CwtSample;
-use crate::{CwtWavelet, ScaletError};
-use num_traits::AsPrimitive;
+use crate::scale_bounds::{WaveletSearch, find_wavelet_peak, linspace};
+use crate::{CwtWavelet, ScaleType, ScaletError};
+use num_complex::Complex;
+use num_traits::{AsPrimitive, Zero};
 use std::cmp::Ordering;
 use std::sync::Arc;
 
+/// How a wavelet scale is mapped to a frequency.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum FrequencyMode {
+    /// FFT bin where the real part of the scaled filter is largest.
+    ///
+    /// Quantized to `fs / filter_length`, neighboring scales of short filters may share a bin.
+    PeakBin,
+    /// Peak of the scaled filter magnitude, refined between FFT bins by parabolic interpolation.
+    Peak,
+    /// Energy centroid `Σ ω|ψ(aω)|² / Σ |ψ(aω)|²` of the scaled filter over positive frequencies.
+    Energy,
+    /// Analytic center frequency `w_peak / (2π a)`, where `w_peak` is the angular frequency
+    /// of the wavelet peak; independent of the filter length.
+    Center,
+}
+
+/// Rejects a sampling frequency that is not finite and positive.
+pub(crate) fn check_sampling_frequency<T: CwtSample>(
+    sampling_frequency: T,
+) -> Result<(), ScaletError> {
+    if !(sampling_frequency > T::zero() && sampling_frequency < T::INFINITY) {
+        return Err(ScaletError::Generic(format!(
+            "Sampling frequency must be finite and positive, but it was {sampling_frequency}"
+        )));
+    }
+    Ok(())
+}
+
+/// Fractional FFT bin a scaled filter is mapped to.
+type BinLocator<T> = fn(&[Complex<T>]) -> T;
+
 pub(crate) fn scale_to_frequencies_impl<T: CwtSample>(
     wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
     scales: &[T],
     sampling_frequency: T,
     filter_length: usize,
+    mode: FrequencyMode,
+    search: &WaveletSearch,
 ) -> Result<Vec<T>, ScaletError>
 where
     usize: AsPrimitive<T>,
     f64: AsPrimitive<T>,
-    isize: AsPrimitive<T>,
 {
+    check_sampling_frequency(sampling_frequency)?;
+    // Bins below filter_length / 2 hold the non-negative frequencies.
+    let positive = (filter_length / 2).max(1);
+    // Locates the bin of a scaled filter, evaluated over its leading `searched` bins.
+    let (locate, searched): (BinLocator<T>, usize) = match mode {
+        FrequencyMode::PeakBin => (real_peak_bin, filter_length),
+        FrequencyMode::Peak => (interpolated_peak_bin, positive),
+        FrequencyMode::Energy => (energy_centroid_bin, positive),
+        FrequencyMode::Center => {
+            return center_frequencies(wavelet, scales, sampling_frequency, search);
+        }
+    };
     if filter_length == 0 {
         return Err(ScaletError::ZeroBaseSized);
     }
     let psi = gen_psi(filter_length)?;
-    let mut max_indices = try_vec![0usize; scales.len()];
+    let mut bins = try_vec![T::zero(); scales.len()];
     let mut current_psi = try_vec![T::zero(); filter_length];
-    for (index, &scale) in max_indices.iter_mut().zip(scales.iter()) {
+    for (bin, &scale) in bins.iter_mut().zip(scales.iter()) {
         for (dst, &psi) in current_psi.iter_mut().zip(psi.iter()) {
             *dst = psi * scale;
         }
@@ -65,20 +111,424 @@ where
                 wavelet_fft.len(),
             ));
         }
-        let idx = wavelet_fft
-            .iter()
-            .enumerate() // gives (index, &value)
-            .max_by(|a, b| a.1.re.partial_cmp(&b.1.re).unwrap_or(Ordering::Equal)) // compare values
-            .map(|(idx, _)| idx);
 
-        *index = idx.unwrap_or(0);
+        *bin = locate(&wavelet_fft[..searched]);
     }
 
     let mut freqs = try_vec![T::zero(); scales.len()];
     let idx_scale = sampling_frequency / filter_length.as_();
-    for (dst, idx) in freqs.iter_mut().zip(&max_indices) {
-        *dst = idx.as_() * idx_scale;
+    for (dst, &bin) in freqs.iter_mut().zip(&bins) {
+        *dst = bin * idx_scale;
     }
 
     Ok(freqs)
 }
+
+/// Analytic center frequencies `w_peak / (2π a)` of `scales`.
+fn center_frequencies<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    scales: &[T],
+    sampling_frequency: T,
+    search: &WaveletSearch,
+) -> Result<Vec<T>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    let (w_peak, _) = find_wavelet_peak(wavelet, search)?;
+    let mut freqs = try_vec![T::zero(); scales.len()];
+    for (dst, &scale) in freqs.iter_mut().zip(scales.iter()) {
+        *dst = w_peak / (T::TWO_PI * scale) * sampling_frequency;
+    }
+    Ok(freqs)
+}
+
+/// Bin where the real part of the filter is largest.
+fn real_peak_bin<T: CwtSample>(filter: &[Complex<T>]) -> T
+where
+    usize: AsPrimitive<T>,
+{
+    let idx = filter
+        .iter()
+        .enumerate() // gives (index, &value)
+        .max_by(|a, b| a.1.re.partial_cmp(&b.1.re).unwrap_or(Ordering::Equal)) // compare values
+        .map(|(idx, _)| idx);
+    idx.unwrap_or(0).as_()
+}
+
+/// Fractional bin of the magnitude peak, refined by a parabola through
+/// the largest bin and its two neighbors.
+fn interpolated_peak_bin<T: CwtSample>(filter: &[Complex<T>]) -> T
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    let magnitude = |i: usize| filter[i].norm_sqr().sqrt();
+    let mut argmax = 0usize;
+    let mut peak = T::NEG_INFINITY;
+    for i in 0..filter.len() {
+        let v = magnitude(i);
+        if v > peak {
+            peak = v;
+            argmax = i;
+        }
+    }
+    if argmax == 0 || argmax + 1 >= filter.len() {
+        return argmax.as_();
+    }
+    let (y0, y1, y2) = (magnitude(argmax - 1), peak, magnitude(argmax + 1));
+    let denominator = y0 - 2f64.as_() * y1 + y2;
+    if denominator >= T::zero() {
+        return argmax.as_();
+    }
+    argmax.as_() + 0.5f64.as_() * (y0 - y2) / denominator
+}
+
+/// Fractional bin of the energy centroid.
+fn energy_centroid_bin<T: CwtSample>(filter: &[Complex<T>]) -> T
+where
+    usize: AsPrimitive<T>,
+{
+    let mut moment = T::zero();
+    let mut energy = T::zero();
+    for (i, v) in filter.iter().enumerate() {
+        let power = v.norm_sqr();
+        moment += i.as_() * power;
+        energy += power;
+    }
+    if energy > T::zero() {
+        moment / energy
+    } else {
+        T::zero()
+    }
+}
+
+/// Angular frequency, at scale 1, the scale of `mode` is inversely proportional to.
+///
+/// This is the continuous counterpart of the grid-based modes, which it matches for long filters.
+fn characteristic_frequency<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    mode: FrequencyMode,
+    search: &WaveletSearch,
+) -> Result<T, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    let (w_peak, peak) = find_wavelet_peak(wavelet.clone(), search)?;
+    if mode != FrequencyMode::Energy {
+        return Ok(w_peak);
+    }
+    // Integrate far enough right of the peak for the tail to be negligible.
+    let magnitude = |w: T| -> Result<T, ScaletError> {
+        let mut response = [Complex::zero()];
+        wavelet.make_wavelet_into(&[w], &mut response)?;
+        Ok(response[0].norm_sqr().sqrt())
+    };
+    let mut w_max = w_peak * 2f64.as_();
+    for _ in 0..32 {
+        if magnitude(w_max)? <= peak * 1e-8f64.as_() {
+            break;
+        }
+        w_max *= 2f64.as_();
+    }
+    let omegas = linspace(T::zero(), w_max, CENTROID_SAMPLES)?;
+    let mut response = try_vec![Complex::zero(); omegas.len()];
+    wavelet.make_wavelet_into(&omegas, &mut response)?;
+    let mut moment = T::zero();
+    let mut energy = T::zero();
+    for (&w, v) in omegas.iter().zip(response.iter()) {
+        let power = v.norm_sqr();
+        moment += w * power;
+        energy += power;
+    }
+    if energy > T::zero() {
+        Ok(moment / energy)
+    } else {
+        Ok(w_peak)
+    }
+}
+
+/// Samples of the grid the energy centroid of the continuous wavelet is integrated on.
+const CENTROID_SAMPLES: usize = 65537;
+
+pub(crate) fn frequencies_to_scales_impl<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    frequencies: &[T],
+    sampling_frequency: T,
+    mode: FrequencyMode,
+    search: &WaveletSearch,
+) -> Result<Vec<T>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    check_sampling_frequency(sampling_frequency)?;
+    if let Some(frequency) = frequencies
+        .iter()
+        .find(|&&x| !(x > T::zero() && x < T::INFINITY))
+    {
+        return Err(ScaletError::Generic(format!(
+            "Frequencies must be finite and positive, but one was {frequency}"
+        )));
+    }
+    let w_c = characteristic_frequency(wavelet, mode, search)?;
+    let mut scales = try_vec![T::zero(); frequencies.len()];
+    for (dst, &frequency) in scales.iter_mut().zip(frequencies.iter()) {
+        *dst = w_c * sampling_frequency / (T::TWO_PI * frequency);
+    }
+    Ok(scales)
+}
+
+/// Band of frequencies the CWT rows are placed at, in Hz.
+///
+/// Frequencies are converted to scales with the peak (center) frequency of the wavelet,
+/// a scale `a` resonates at `f = w_peak * sampling_frequency / (2π a)`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FrequencyRange {
+    /// Lowest analyzed frequency in Hz, always the last row.
+    pub f_min: f64,
+    /// Highest analyzed frequency in Hz, at most the Nyquist frequency.
+    pub f_max: f64,
+    /// Sampling frequency of the input in Hz.
+    pub sampling_frequency: f64,
+    /// Distribution of the frequencies.
+    ///
+    /// - [`ScaleType::Log`]: `f_min * 2^(k / nv)` up to `f_max`, `nv` is the **voices per octave**.
+    /// - [`ScaleType::Linear`]: uniform spacing from `f_min` to `f_max` inclusive,
+    ///   `nv` is the **total number of frequencies**.
+    pub spacing: ScaleType,
+    /// Voices per octave or total number of frequencies, see [`spacing`](Self::spacing).
+    pub nv: usize,
+}
+
+impl FrequencyRange {
+    /// Returns the row frequencies in Hz, in **descending** order, the same orientation
+    /// as the ascending scales of an executor built from this range.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if the bounds are not finite and positive, if `f_min > f_max`,
+    /// if `f_max` is above the Nyquist frequency or if `nv` is zero.
+    pub fn frequencies(&self) -> Result<Vec<f64>, ScaletError> {
+        check_sampling_frequency(self.sampling_frequency)?;
+        if !(self.f_min.is_finite() && self.f_max.is_finite())
+            || self.f_min <= 0.
+            || self.f_min > self.f_max
+        {
+            return Err(ScaletError::Generic(format!(
+                "Invalid frequency range [{}, {}]",
+                self.f_min, self.f_max
+            )));
+        }
+        let nyquist = self.sampling_frequency * 0.5;
+        if self.f_max > nyquist {
+            return Err(ScaletError::Generic(format!(
+                "Frequency {} is above the Nyquist frequency {nyquist}",
+                self.f_max
+            )));
+        }
+        if self.nv == 0 {
+            return Err(ScaletError::ZeroBaseSized);
+        }
+        let mut frequencies = match self.spacing {
+            ScaleType::Log => {
+                // Small slack so that an exact octave multiple keeps its last voice.
+                let octaves = (self.f_max / self.f_min).log2();
+                let count = (octaves * self.nv as f64 + 1e-9).floor() as usize + 1;
+                let mut frequencies = try_vec![0f64; count];
+                for (k, dst) in frequencies.iter_mut().enumerate() {
+                    *dst = self.f_min * (k as f64 / self.nv as f64).exp2();
+                }
+                frequencies
+            }
+            ScaleType::Linear => linspace(self.f_min, self.f_max, self.nv)?,
+        };
+        frequencies.reverse();
+        Ok(frequencies)
+    }
+}
+
+/// Converts a frequency range into ascending scales of `wavelet`.
+pub(crate) fn frequency_range_scales<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    range: FrequencyRange,
+    search: &WaveletSearch,
+) -> Result<Vec<T>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    let frequencies = range.frequencies()?;
+    let (w_peak, _) = find_wavelet_peak(wavelet, search)?;
+    let mut scales = try_vec![T::zero(); frequencies.len()];
+    for (dst, &frequency) in scales.iter_mut().zip(frequencies.iter()) {
+        let cycles: T = (frequency / range.sampling_frequency).as_();
+        *dst = w_peak / (T::TWO_PI * cycles);
+    }
+    Ok(scales)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CwtOptions, MorletWavelet, Scalet};
+
+    fn morlet() -> Arc<dyn CwtWavelet<f64> + Send + Sync> {
+        Arc::new(MorletWavelet::default())
+    }
+
+    fn max_relative_error(actual: &[f64], expected: &[f64]) -> f64 {
+        assert_eq!(actual.len(), expected.len());
+        actual
+            .iter()
+            .zip(expected.iter())
+            .map(|(a, b)| ((a - b) / b).abs())
+            .fold(0., f64::max)
+    }
+
+    #[test]
+    fn center_frequencies_invert_to_scales() -> Result<(), ScaletError> {
+        let search = WaveletSearch::default();
+        let scales = [0.8, 2.5, 7., 31.3, 250.];
+        let frequencies = Scalet::scales_to_frequencies_with_mode_f64(
+            morlet(),
+            &scales,
+            0,
+            1000.,
+            FrequencyMode::Center,
+            &search,
+        )?;
+        assert!(frequencies.windows(2).all(|w| w[0] > w[1]));
+        let restored = Scalet::frequencies_to_scales_f64(
+            morlet(),
+            &frequencies,
+            1000.,
+            FrequencyMode::Center,
+            &search,
+        )?;
+        assert!(max_relative_error(&restored, &scales) < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn grid_modes_converge_to_their_inverse() -> Result<(), ScaletError> {
+        let search = WaveletSearch::default();
+        let scales = [8., 12.5, 40., 160.];
+        for mode in [FrequencyMode::Peak, FrequencyMode::Energy] {
+            let frequencies = Scalet::scales_to_frequencies_with_mode_f64(
+                morlet(),
+                &scales,
+                1 << 14,
+                1.,
+                mode,
+                &search,
+            )?;
+            let restored =
+                Scalet::frequencies_to_scales_f64(morlet(), &frequencies, 1., mode, &search)?;
+            assert!(max_relative_error(&restored, &scales) < 1e-3);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn frequency_executor_rows_sit_at_the_range() -> Result<(), ScaletError> {
+        let options = CwtOptions::default();
+        for spacing in [ScaleType::Log, ScaleType::Linear] {
+            let range = FrequencyRange {
+                f_min: 2.,
+                f_max: 120.,
+                sampling_frequency: 500.,
+                spacing,
+                nv: 12,
+            };
+            let executor = Scalet::make_cwt_for_frequencies_f64(morlet(), 1024, range, options)?;
+            let frequencies = Scalet::scales_to_frequencies_with_mode_f64(
+                morlet(),
+                executor.view_scales(),
+                0,
+                range.sampling_frequency,
+                FrequencyMode::Center,
+                &options.wavelet_search,
+            )?;
+            assert!(max_relative_error(&frequencies, &range.frequencies()?) < 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn tones_peak_in_their_frequency_rows() -> Result<(), ScaletError> {
+        let range = FrequencyRange {
+            f_min: 20.,
+            f_max: 160.,
+            sampling_frequency: 1000.,
+            spacing: ScaleType::Linear,
+            nv: 8,
+        };
+        let executor =
+            Scalet::make_cwt_for_frequencies_f64(morlet(), 2048, range, CwtOptions::default())?;
+        let frequencies = range.frequencies()?;
+        assert_eq!(executor.view_scales().len(), frequencies.len());
+        for (row, &frequency) in frequencies.iter().enumerate() {
+            let tone = (0..2048)
+                .map(|i| (2. * std::f64::consts::PI * frequency * i as f64 / 1000.).sin())
+                .collect::<Vec<f64>>();
+            let power = executor.execute_power(&tone)?;
+            let energy = power
+                .iter()
+                .map(|x| x[512..1536].iter().sum::<f64>())
+                .collect::<Vec<f64>>();
+            let loudest = energy
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (i, &e)| if e > energy[acc] { i } else { acc });
+            assert_eq!(loudest, row);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_frequencies_are_rejected() {
+        let search = WaveletSearch::default();
+        for fs in [0., -1., f64::NAN, f64::INFINITY] {
+            assert!(check_sampling_frequency(fs).is_err());
+            for mode in [
+                FrequencyMode::PeakBin,
+                FrequencyMode::Peak,
+                FrequencyMode::Energy,
+                FrequencyMode::Center,
+            ] {
+                assert!(
+                    Scalet::scales_to_frequencies_with_mode_f64(
+                        morlet(),
+                        &[2., 8.],
+                        256,
+                        fs,
+                        mode,
+                        &search
+                    )
+                    .is_err()
+                );
+            }
+        }
+        for frequency in [0., -3., f64::NAN] {
+            assert!(
+                Scalet::frequencies_to_scales_f64(
+                    morlet(),
+                    &[10., frequency],
+                    100.,
+                    FrequencyMode::Center,
+                    &search
+                )
+                .is_err()
+            );
+        }
+        let range = FrequencyRange {
+            f_min: 1.,
+            f_max: 60.,
+            sampling_frequency: 100.,
+            spacing: ScaleType::Log,
+            nv: 4,
+        };
+        assert!(range.frequencies().is_err());
+    }
+}
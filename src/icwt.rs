@@ -0,0 +1,182 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::err::try_vec;
+use crate::mla::fmla;
+use crate::sample::CwtSample;
+use crate::{CwtWavelet, ScaletError};
+use num_complex::Complex;
+use num_traits::{AsPrimitive, Zero};
+
+/// Selects the inversion formula used to reconstruct a signal from its CWT coefficients.
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ReconstructionFormula {
+    /// **Single-integral (Morlet) formula:** the signal is recovered by summing the
+    /// real part of the coefficients across scales.
+    ///
+    /// This is the fastest option and requires no additional FFTs, but it assumes
+    /// the wavelet is analytic (zero for negative frequencies), executors with a
+    /// non-analytic wavelet reject it.
+    #[default]
+    SingleIntegral,
+    /// **Double-integral formula:** every scale is convolved back with the wavelet
+    /// before the scales are summed.
+    ///
+    /// This is the classical inverse CWT and works for any admissible wavelet, at the
+    /// cost of one forward FFT per scale. Positive and negative frequencies are normalized
+    /// by the admissibility constant of their own half-axis, so a wavelet that also
+    /// responds to negative frequencies recovers the full signal. When the executor pads the signal, coefficients
+    /// outside the signal are unknown and taken as zeros, which makes this formula less
+    /// accurate than the single integral at large scales.
+    DoubleIntegral,
+}
+
+// Integration nodes span omega from 1e-5 to 1e4 uniformly in log-frequency.
+const INTEGRATION_NODES: usize = 65536;
+const INTEGRATION_LOG_START: f64 = -11.512925464970229;
+const INTEGRATION_LOG_END: f64 = 9.210340371976184;
+
+fn integrate_over_log_frequency<T: CwtSample, F: Fn(Complex<T>) -> Complex<T>>(
+    wavelet: &(dyn CwtWavelet<T> + Send + Sync),
+    negative: bool,
+    integrand: F,
+) -> Result<Complex<T>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    // ∫ f(ω) / ω dω over (0, ∞) becomes ∫ f(e^u) du after substituting ω = e^u,
+    // which samples the low and high frequency tails equally well.
+    // The negative half-axis is the same integral over ω = -e^u.
+    let du: T =
+        ((INTEGRATION_LOG_END - INTEGRATION_LOG_START) / (INTEGRATION_NODES - 1) as f64).as_();
+    let mut omegas = try_vec![T::zero(); INTEGRATION_NODES];
+    for (i, dst) in omegas.iter_mut().enumerate() {
+        *dst = fmla(i.as_(), du, INTEGRATION_LOG_START.as_()).exp();
+        if negative {
+            *dst = -*dst;
+        }
+    }
+    let psi = wavelet.make_wavelet(&omegas)?;
+    if psi.len() != omegas.len() {
+        return Err(ScaletError::WaveletInvalidSize(omegas.len(), psi.len()));
+    }
+    let mut acc = Complex::<T>::zero();
+    for (i, &v) in psi.iter().enumerate() {
+        let w = if i == 0 || i == INTEGRATION_NODES - 1 {
+            0.5f64.as_()
+        } else {
+            1f64.as_()
+        };
+        acc = acc + integrand(v) * w;
+    }
+    Ok(acc * du)
+}
+
+/// Admissibility constant of the single-integral formula, `C = ∫ conj(ψ̂(ω)) / ω dω`.
+pub(crate) fn single_integral_constant<T: CwtSample>(
+    wavelet: &(dyn CwtWavelet<T> + Send + Sync),
+) -> Result<Complex<T>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    let c = integrate_over_log_frequency(wavelet, false, |v| v.conj())?;
+    if c.norm_sqr() <= T::zero() {
+        return Err(ScaletError::Generic(
+            "Wavelet has zero single-integral admissibility constant and cannot be inverted"
+                .to_string(),
+        ));
+    }
+    Ok(c)
+}
+
+/// Admissibility constant of the double-integral formula, `C = ∫ |ψ̂(ω)|² / ω dω`.
+pub(crate) fn double_integral_constant<T: CwtSample>(
+    wavelet: &(dyn CwtWavelet<T> + Send + Sync),
+) -> Result<T, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    let c =
+        integrate_over_log_frequency(wavelet, false, |v| Complex::new(v.norm_sqr(), T::zero()))?;
+    if c.re <= T::zero() {
+        return Err(ScaletError::Generic(
+            "Wavelet has zero double-integral admissibility constant and cannot be inverted"
+                .to_string(),
+        ));
+    }
+    Ok(c.re)
+}
+
+/// Double-integral admissibility constant of the negative half-axis,
+/// `C₋ = ∫ |ψ̂(-ω)|² / ω dω`, zero for analytic wavelets.
+///
+/// Values negligible next to `positive`, the constant of the positive half-axis, are
+/// returned as zero: such a wavelet does not capture the negative frequencies either.
+pub(crate) fn negative_double_integral_constant<T: CwtSample>(
+    wavelet: &(dyn CwtWavelet<T> + Send + Sync),
+    positive: T,
+) -> Result<T, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    if wavelet.is_analytic() {
+        return Ok(T::zero());
+    }
+    let c = integrate_over_log_frequency(wavelet, true, |v| Complex::new(v.norm_sqr(), T::zero()))?;
+    if c.re <= positive * T::EPSILON {
+        return Ok(T::zero());
+    }
+    Ok(c.re)
+}
+
+/// Quadrature weights `d(ln a)` of the scale grid.
+///
+/// For a logarithmic grid every weight equals `ln(2) / nv`, for a linear one
+/// it is `Δa / a`, any other monotonic grid is handled by central differences.
+pub(crate) fn log_scale_weights<T: CwtSample>(scales: &[T]) -> Result<Vec<T>, ScaletError>
+where
+    f64: AsPrimitive<T>,
+{
+    let mut weights = try_vec![T::zero(); scales.len()];
+    if scales.len() < 2 {
+        weights.fill(1f64.as_());
+        return Ok(weights);
+    }
+    let last = scales.len() - 1;
+    for (i, dst) in weights.iter_mut().enumerate() {
+        let lo = scales[i.saturating_sub(1)];
+        let hi = scales[(i + 1).min(last)];
+        let span = if i == 0 || i == last { 1 } else { 2 };
+        *dst = ((hi / lo).ln() / (span as f64).as_()).abs();
+    }
+    Ok(weights)
+}
@@ -33,39 +33,70 @@
     feature(stdarch_neon_fcma)
 )]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+mod analysis;
 #[cfg(all(target_arch = "x86_64", feature = "avx"))]
 mod avx;
+mod coherence;
 mod cwt_executor;
 mod cwt_filter;
+mod denoise;
 #[cfg(feature = "scalogram")]
 mod drawing;
 mod err;
 mod factory;
 mod freqs;
+mod icwt;
+mod mask;
 mod mla;
 #[cfg(all(target_arch = "aarch64", feature = "neon"))]
 mod neon;
+mod padding;
+mod phase;
+mod ridge;
 mod sample;
 mod scale_bounds;
 mod scales;
+mod significance;
+mod spectra;
 mod spetrum_arith;
 #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "sse"))]
 mod sse;
+mod ssq;
+mod stream;
+mod time_support;
 mod wavelets;
+mod xwt;
 
 #[cfg(feature = "scalogram")]
 #[cfg_attr(docsrs, doc(cfg(feature = "scalogram")))]
 use crate::drawing::{draw_scalogram_color_impl_f32, draw_scalogram_color_impl_f64};
-use crate::factory::create_cwt;
-use crate::freqs::scale_to_frequencies_impl;
+use crate::factory::{
+    create_cwt, create_cwt_stream, create_cwt_with_scales, create_ssq, make_length_scales,
+    make_scale_bounds, scale_grid,
+};
+use crate::freqs::{frequencies_to_scales_impl, frequency_range_scales, scale_to_frequencies_impl};
+pub use analysis::CwtAnalysis;
+pub use coherence::{Coherence, CoherenceOptions};
 pub use cwt_filter::CwtWavelet;
+pub use denoise::{DenoiseOptions, DenoiseThreshold, NoiseEstimate, Thresholding};
 #[cfg(feature = "scalogram")]
 #[cfg_attr(docsrs, doc(cfg(feature = "scalogram")))]
 pub use drawing::Colormap;
 pub use err::ScaletError;
+pub use freqs::{FrequencyMode, FrequencyRange};
+pub use icwt::ReconstructionFormula;
+pub use mask::TimeFrequencyMask;
 use num_complex::Complex;
+pub use padding::PaddingMode;
+pub use ridge::{Ridge, RidgeOptions};
+pub use scale_bounds::{ScaleBounds, ScaleCutoffs, WaveletSearch};
+pub use significance::{NoiseModel, Significance, SignificanceOptions};
+pub use spectra::{GlobalSpectrum, ScaleBand};
+pub use ssq::{SsqCwt, SsqExecutor, SsqOptions};
 use std::sync::Arc;
+pub use stream::{CwtStreamExecutor, CwtStreamOptions};
 pub use wavelets::{CmhatWavelet, GaborWavelet, HhhatWavelet, MorletWavelet};
+pub use xwt::CrossWavelet;
 
 /// Configuration options for the Continuous Wavelet Transform (CWT).
 ///
@@ -80,7 +111,7 @@ pub struct CwtOptions {
     /// Determines how scales are distributed (e.g. logarithmic or linear)
     /// and how `nv` is interpreted.
     pub scale_type: ScaleType,
-    /// Number of voices per octave **or** total number of scales.
+    /// Number of voices per octave.
     ///
     /// The exact meaning depends on `scale_type`:
     /// - For logarithmic scales, `nv` specifies the number of voices per octave.
@@ -93,6 +124,31 @@ pub struct CwtOptions {
     /// distribution of energies and component amplitudes than L2.
     /// If false (default true), uses L2 norm.
     pub l1_norm: bool,
+    /// How the signal is extended beyond its boundaries before convolution.
+    ///
+    /// Defaults to [`PaddingMode::None`], no extension: the convolution is circular over
+    /// the internal FFT length, which may be zero-extended past the signal. Any other mode
+    /// pads internally and the output still has one column per input sample.
+    pub padding: PaddingMode,
+    /// Whether to generate the frequency-domain filters of all scales once, when
+    /// the executor is built, instead of on every call.
+    ///
+    /// Trades [`CwtExecutor::filter_bank_size`] bytes of memory for faster repeated runs.
+    /// Default false.
+    pub precompute_filters: bool,
+    /// Number of threads [`CwtExecutor::execute`] spreads the scales across.
+    ///
+    /// Every worker owns its scratch buffers and writes a disjoint set of rows.
+    /// Default 1, a single-threaded run.
+    pub thread_count: usize,
+    /// Cutoffs bounding the automatically generated scales.
+    ///
+    /// The largest scale is derived from the FFT length of the executor, so longer
+    /// signals reach lower frequencies. Ignored when scales are given explicitly.
+    pub scale_cutoffs: ScaleCutoffs,
+    /// Tolerances of the searches locating the wavelet peak and cutoff frequencies,
+    /// used for scale bounds and scale-to-frequency conversion.
+    pub wavelet_search: WaveletSearch,
 }
 
 impl Default for CwtOptions {
@@ -101,6 +157,11 @@ impl Default for CwtOptions {
             nv: 32,
             scale_type: ScaleType::Log,
             l1_norm: true,
+            padding: PaddingMode::None,
+            precompute_filters: false,
+            thread_count: 1,
+            scale_cutoffs: ScaleCutoffs::default(),
+            wavelet_search: WaveletSearch::default(),
         }
     }
 }
@@ -116,6 +177,9 @@ pub trait CwtExecutor<T> {
     /// corresponds to the wavelet coefficients for one scale (row), containing coefficients
     /// across the time axis (columns).
     /// The resulting dimensions are: `[num_scales, input_length]`.
+    ///
+    /// The input may have any length from one sample up to [`length`](Self::length);
+    /// it is padded internally to the executor's FFT size and the output is cropped back.
     fn execute(&self, input: &[T]) -> Result<Vec<Vec<Complex<T>>>, ScaletError>;
     /// Executes the Continuous Wavelet Transform on a **complex-valued** input signal.
     ///
@@ -130,13 +194,139 @@ pub trait CwtExecutor<T> {
     /// layout and interpretation as [`execute`](Self::execute).
     ///
     /// # Errors
-    /// Returns `ScaletError` if the input length is incompatible with the
-    /// executor configuration or if an internal FFT operation fails.
+    /// Returns `ScaletError` if the input is empty or longer than the executor
+    /// length, or if an internal FFT operation fails.
     fn execute_complex(&self, input: &[Complex<T>]) -> Result<Vec<Vec<Complex<T>>>, ScaletError>;
-    /// Returns the expected length of the input signal this executor was built for.
+    /// Executes the Continuous Wavelet Transform into a caller-provided buffer without allocating.
+    ///
+    /// The coefficients are written row-major, one row of `input.len()` values per scale,
+    /// so `out` must hold exactly `num_scales * input.len()` values.
+    /// The work always runs on the calling thread, regardless of [`CwtOptions::thread_count`].
+    ///
+    /// # Parameters
+    /// - `input`: Real-valued signal, up to [`length`](Self::length) samples.
+    /// - `out`: Flat `[num_scales, input_length]` output.
+    /// - `scratch`: Workspace of at least [`scratch_length`](Self::scratch_length) values.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if the input is empty or too long, if `out` or `scratch`
+    /// have an unexpected size, or if an internal FFT operation fails.
+    fn execute_into(
+        &self,
+        input: &[T],
+        out: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), ScaletError>;
+    /// Complex-valued counterpart of [`execute_into`](Self::execute_into).
+    fn execute_complex_into(
+        &self,
+        input: &[Complex<T>],
+        out: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), ScaletError>;
+    /// Executes the Continuous Wavelet Transform on several equal-length channels.
+    ///
+    /// FFT plans and scales are shared, and every scale filter is generated once per batch
+    /// (once per worker with [`CwtOptions::thread_count`]) instead of once per channel.
+    ///
+    /// # Returns
+    /// One scalogram per channel, laid out as `[channel][scale][time]`.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if the batch is empty, the channels differ in length or are
+    /// longer than the executor length, or if an internal FFT operation fails.
+    fn execute_batch(&self, channels: &[&[T]]) -> Result<Vec<Vec<Vec<Complex<T>>>>, ScaletError>;
+    /// Same as [`execute_batch`](Self::execute_batch), with the output in a single
+    /// row-major `[channel][scale][time]` allocation.
+    fn execute_batch_flat(&self, channels: &[&[T]]) -> Result<Vec<Complex<T>>, ScaletError>;
+    /// Executes the Continuous Wavelet Transform and returns the power `|W(a, b)|²`.
+    ///
+    /// The power of every scale is taken right after its inverse FFT,
+    /// so the complex scalogram is never held in memory.
+    ///
+    /// # Returns
+    /// A two-dimensional vector laid out as `[scale][time]`, like [`execute`](Self::execute).
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if the input is empty or longer than the executor
+    /// length, or if an internal FFT operation fails.
+    fn execute_power(&self, input: &[T]) -> Result<Vec<Vec<T>>, ScaletError>;
+    /// Same as [`execute_power`](Self::execute_power), returning the magnitude `|W(a, b)|`.
+    fn execute_magnitude(&self, input: &[T]) -> Result<Vec<Vec<T>>, ScaletError>;
+    /// Allocation-free counterpart of [`execute_power`](Self::execute_power).
+    ///
+    /// `out` is a flat `[num_scales, input_length]` buffer and `scratch` must hold at least
+    /// [`scratch_length`](Self::scratch_length) values, as in [`execute_into`](Self::execute_into).
+    fn execute_power_into(
+        &self,
+        input: &[T],
+        out: &mut [T],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), ScaletError>;
+    /// Allocation-free counterpart of [`execute_magnitude`](Self::execute_magnitude).
+    fn execute_magnitude_into(
+        &self,
+        input: &[T],
+        out: &mut [T],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), ScaletError>;
+    /// Returns the scratch size [`execute_into`](Self::execute_into),
+    /// [`execute_complex_into`](Self::execute_complex_into) and the real-valued
+    /// `_into` variants require.
+    fn scratch_length(&self) -> usize;
+    /// Reconstructs a **real-valued** time-domain signal from its CWT coefficients.
+    ///
+    /// Uses the executor's own scales, wavelet and normalization. The admissibility
+    /// constant of the chosen formula is derived from the wavelet on first use and cached.
+    ///
+    /// # Parameters
+    /// - `coeffs`: Coefficients laid out as produced by [`execute`](Self::execute),
+    ///   `[num_scales, input_length]`. They may be modified (e.g. masked) beforehand.
+    ///   Rows may be of any length up to [`length`](Self::length).
+    /// - `formula`: Single- or double-integral inversion, see [`ReconstructionFormula`].
+    ///
+    /// # Returns
+    /// The reconstructed signal, one sample per coefficient column. The DC component
+    /// (signal mean) is not captured by the CWT and is therefore not restored.
+    ///
+    /// # Accuracy
+    /// With [`PaddingMode::None`] and a signal that fills the internal FFT length the
+    /// reconstruction is exact up to the discretization of the scale integral. Otherwise
+    /// it is approximate: the forward transform
+    /// used the padded or zero-extended extent of the signal, but only the coefficients of
+    /// the signal itself are returned, and the extension is missing from the reconstruction. The error is
+    /// largest near the edges; on a full signal expect a few percent RMS with the
+    /// single-integral formula and around ten percent with the double-integral one,
+    /// which convolves every row with the wavelet across the missing region.
     ///
-    /// This is typically used to pre-calculate necessary internal parameters or
-    /// check against the input signal length during `execute`.
+    /// # Errors
+    /// Returns `ScaletError` if the coefficient layout does not match the executor,
+    /// if the wavelet is not admissible, if [`ReconstructionFormula::SingleIntegral`] is
+    /// requested with a non-analytic wavelet, or if an internal FFT operation fails.
+    fn inverse(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<T>, ScaletError>;
+    /// Reconstructs a **complex-valued** time-domain signal from its CWT coefficients.
+    ///
+    /// This is the counterpart of [`execute_complex`](Self::execute_complex). With an
+    /// analytic wavelet, like the bundled ones, only the positive-frequency content of the
+    /// signal is recovered; for a real input this equals half of its analytic signal.
+    /// With a wavelet that also responds to negative frequencies,
+    /// [`ReconstructionFormula::DoubleIntegral`] recovers the whole signal.
+    ///
+    /// See [`inverse`](Self::inverse) for parameters and errors.
+    fn inverse_complex(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<Complex<T>>, ScaletError>;
+    /// Returns the maximum length of the input signal this executor was built for.
+    ///
+    /// Internally the executor runs its FFTs at the next `2^a·3^b·5^c` size that fits
+    /// this length plus padding, so any length is efficient. Shorter inputs are
+    /// accepted as well and padded transparently.
     fn length(&self) -> usize;
     /// Provides a zero-copy view of the scale values used for this CWT instance.
     ///
@@ -147,6 +337,12 @@ pub trait CwtExecutor<T> {
     ///
     /// An immutable slice (`&[T]`) containing the pre-calculated scale values.
     fn view_scales(&self) -> &[T];
+
+    /// Returns the memory, in bytes, taken by the precomputed wavelet filters.
+    ///
+    /// The estimate is the same whether or not [`CwtOptions::precompute_filters`] was set,
+    /// so it can be queried on a regular executor before deciding to build one that caches them.
+    fn filter_bank_size(&self) -> usize;
 }
 
 /// The main entry point for constructing CWT executors.
@@ -159,20 +355,20 @@ impl Scalet {
     /// Creates a CWT executor configured for single-precision floating-point numbers (`f32`)
     /// using the default **Morlet Wavelet**.
     ///
-    /// The resulting `CwtExecutor` is wrapped in an `Arc` for thread-safe sharing and
-    /// object-safe dynamic dispatch.
+    /// The resulting executor also provides the [`CwtAnalysis`] methods, and is wrapped in
+    /// an `Arc` for thread-safe sharing and object-safe dynamic dispatch.
     ///
     /// # Arguments
     ///
-    /// * `length` - The expected length of the signal the executor will process.
+    /// * `length` - The maximum length of the signal the executor will process.
     ///
     /// # Returns
     ///
-    /// A `Result` containing an `Arc<dyn CwtExecutor<f32>>` or a `ScaletError`.
+    /// A `Result` containing an `Arc<dyn CwtAnalysis<f32>>` or a `ScaletError`.
     pub fn make_morlet_f32(
         length: usize,
         options: CwtOptions,
-    ) -> Result<Arc<dyn CwtExecutor<f32> + Send + Sync>, ScaletError> {
+    ) -> Result<Arc<dyn CwtAnalysis<f32> + Send + Sync>, ScaletError> {
         create_cwt(
             Arc::new(MorletWavelet::default()),
             length,
@@ -189,15 +385,15 @@ impl Scalet {
     ///
     /// # Arguments
     ///
-    /// * `length` - The expected length of the signal the executor will process.
+    /// * `length` - The maximum length of the signal the executor will process.
     ///
     /// # Returns
     ///
-    /// A `Result` containing an `Arc<dyn CwtExecutor<f64>>` or a `ScaletError`.
+    /// A `Result` containing an `Arc<dyn CwtAnalysis<f64>>` or a `ScaletError`.
     pub fn make_morlet_f64(
         length: usize,
         options: CwtOptions,
-    ) -> Result<Arc<dyn CwtExecutor<f64> + Send + Sync>, ScaletError> {
+    ) -> Result<Arc<dyn CwtAnalysis<f64> + Send + Sync>, ScaletError> {
         create_cwt(
             Arc::new(MorletWavelet::default()),
             length,
@@ -208,21 +404,25 @@ impl Scalet {
 
     /// Creates a CWT executor for **single-precision (`f32`)** using a custom wavelet.
     ///
+    /// Every CWT executor returned by `Scalet` implements [`CwtAnalysis`] on top of
+    /// [`CwtExecutor`], and converts to `Arc<dyn CwtExecutor<f32> + Send + Sync>` where
+    /// only the transform is needed.
+    ///
     /// # Arguments
     ///
     /// * `wavelet` – A reference-counted, thread-safe wavelet implementing `CwtWavelet<f32>`.
-    /// * `length` – The expected length of the signal the executor will process.
+    /// * `length` – The maximum length of the signal the executor will process.
     /// * `options` – CWT configuration parameters controlling scales, voices, and normalization.
     ///
     /// # Returns
     ///
-    /// A `Result` containing an `Arc<dyn CwtExecutor<f32> + Send + Sync>` on success,
+    /// A `Result` containing an `Arc<dyn CwtAnalysis<f32> + Send + Sync>` on success,
     /// or a `ScaletError` if creation fails.
     pub fn make_cwt_f32(
         wavelet: Arc<dyn CwtWavelet<f32> + Send + Sync>,
         length: usize,
         options: CwtOptions,
-    ) -> Result<Arc<dyn CwtExecutor<f32> + Send + Sync>, ScaletError> {
+    ) -> Result<Arc<dyn CwtAnalysis<f32> + Send + Sync>, ScaletError> {
         create_cwt(wavelet, length, options.scale_type, options)
     }
 
@@ -231,21 +431,256 @@ impl Scalet {
     /// # Arguments
     ///
     /// * `wavelet` – A reference-counted, thread-safe wavelet implementing `CwtWavelet<f64>`.
-    /// * `length` – The expected length of the signal the executor will process.
+    /// * `length` – The maximum length of the signal the executor will process.
     /// * `options` – CWT configuration parameters controlling scales, voices, and normalization.
     ///
     /// # Returns
     ///
-    /// A `Result` containing an `Arc<dyn CwtExecutor<f64> + Send + Sync>` on success,
+    /// A `Result` containing an `Arc<dyn CwtAnalysis<f64> + Send + Sync>` on success,
     /// or a `ScaletError` if creation fails.
     pub fn make_cwt_f64(
         wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync>,
         length: usize,
         options: CwtOptions,
-    ) -> Result<Arc<dyn CwtExecutor<f64> + Send + Sync>, ScaletError> {
+    ) -> Result<Arc<dyn CwtAnalysis<f64> + Send + Sync>, ScaletError> {
         create_cwt(wavelet, length, options.scale_type, options)
     }
 
+    /// Creates a CWT executor for **single-precision (`f32`)** over explicit scales.
+    ///
+    /// The rows of the output follow `scales` exactly, `options.scale_type` and
+    /// `options.nv` are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `wavelet` – A reference-counted, thread-safe wavelet implementing `CwtWavelet<f32>`.
+    /// * `length` – The maximum length of the signal the executor will process.
+    /// * `scales` – Scales of the output rows, every one finite and positive.
+    /// * `options` – CWT configuration parameters controlling normalization and execution.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing an `Arc<dyn CwtAnalysis<f32> + Send + Sync>` on success,
+    /// or a `ScaletError` if `scales` is empty or invalid, or if creation fails.
+    pub fn make_cwt_with_scales_f32(
+        wavelet: Arc<dyn CwtWavelet<f32> + Send + Sync>,
+        length: usize,
+        scales: &[f32],
+        options: CwtOptions,
+    ) -> Result<Arc<dyn CwtAnalysis<f32> + Send + Sync>, ScaletError> {
+        create_cwt_with_scales(wavelet, length, scales, options)
+    }
+
+    /// Creates a CWT executor for **double-precision (`f64`)** over explicit scales.
+    ///
+    /// Same behavior and parameters as [`Scalet::make_cwt_with_scales_f32`], but for `f64` data.
+    pub fn make_cwt_with_scales_f64(
+        wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync>,
+        length: usize,
+        scales: &[f64],
+        options: CwtOptions,
+    ) -> Result<Arc<dyn CwtAnalysis<f64> + Send + Sync>, ScaletError> {
+        create_cwt_with_scales(wavelet, length, scales, options)
+    }
+
+    /// Creates a CWT executor for **single-precision (`f32`)** whose rows sit at the
+    /// frequencies of `range`.
+    ///
+    /// Row `i` of the output analyzes `range.frequencies()[i]`, high frequencies first.
+    /// `options.scale_type` and `options.nv` are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `wavelet` – A reference-counted, thread-safe wavelet implementing `CwtWavelet<f32>`.
+    /// * `length` – The maximum length of the signal the executor will process.
+    /// * `range` – Frequency band, sampling frequency and spacing of the rows.
+    /// * `options` – CWT configuration parameters controlling normalization and execution.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing an `Arc<dyn CwtAnalysis<f32> + Send + Sync>` on success,
+    /// or a `ScaletError` if the range is invalid or creation fails.
+    pub fn make_cwt_for_frequencies_f32(
+        wavelet: Arc<dyn CwtWavelet<f32> + Send + Sync>,
+        length: usize,
+        range: FrequencyRange,
+        options: CwtOptions,
+    ) -> Result<Arc<dyn CwtAnalysis<f32> + Send + Sync>, ScaletError> {
+        let scales = frequency_range_scales(wavelet.clone(), range, &options.wavelet_search)?;
+        create_cwt_with_scales(wavelet, length, &scales, options)
+    }
+
+    /// Creates a CWT executor for **double-precision (`f64`)** whose rows sit at the
+    /// frequencies of `range`.
+    ///
+    /// Same behavior and parameters as [`Scalet::make_cwt_for_frequencies_f32`], but for `f64` data.
+    pub fn make_cwt_for_frequencies_f64(
+        wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync>,
+        length: usize,
+        range: FrequencyRange,
+        options: CwtOptions,
+    ) -> Result<Arc<dyn CwtAnalysis<f64> + Send + Sync>, ScaletError> {
+        let scales = frequency_range_scales(wavelet.clone(), range, &options.wavelet_search)?;
+        create_cwt_with_scales(wavelet, length, &scales, options)
+    }
+
+    /// Creates a Synchrosqueezed CWT executor for **single-precision (`f32`)**.
+    ///
+    /// # Arguments
+    ///
+    /// * `wavelet` – A reference-counted, thread-safe wavelet implementing `CwtWavelet<f32>`.
+    /// * `length` – The maximum length of the signal the executor will process.
+    /// * `options` – SSQ configuration: underlying CWT options, frequency grid and threshold.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing an `Arc<dyn SsqExecutor<f32> + Send + Sync>` on success,
+    /// or a `ScaletError` if the sampling frequency is not finite and positive, `gamma`
+    /// is negative or not finite, or creation fails.
+    pub fn make_ssq_f32(
+        wavelet: Arc<dyn CwtWavelet<f32> + Send + Sync>,
+        length: usize,
+        options: SsqOptions,
+    ) -> Result<Arc<dyn SsqExecutor<f32> + Send + Sync>, ScaletError> {
+        create_ssq(wavelet, length, options)
+    }
+
+    /// Creates a Synchrosqueezed CWT executor for **double-precision (`f64`)**.
+    ///
+    /// Same behavior and parameters as [`Scalet::make_ssq_f32`], but for `f64` data.
+    pub fn make_ssq_f64(
+        wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync>,
+        length: usize,
+        options: SsqOptions,
+    ) -> Result<Arc<dyn SsqExecutor<f64> + Send + Sync>, ScaletError> {
+        create_ssq(wavelet, length, options)
+    }
+
+    /// Creates a streaming CWT executor for **single-precision (`f32`)**.
+    ///
+    /// # Arguments
+    ///
+    /// * `wavelet` – A reference-counted, thread-safe wavelet implementing `CwtWavelet<f32>`.
+    /// * `options` – Underlying CWT options, hop size and context tolerance.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `Box<dyn CwtStreamExecutor<f32> + Send + Sync>` on success,
+    /// or a `ScaletError` if creation fails.
+    pub fn make_cwt_stream_f32(
+        wavelet: Arc<dyn CwtWavelet<f32> + Send + Sync>,
+        options: CwtStreamOptions,
+    ) -> Result<Box<dyn CwtStreamExecutor<f32> + Send + Sync>, ScaletError> {
+        create_cwt_stream(wavelet, options)
+    }
+
+    /// Creates a streaming CWT executor for **double-precision (`f64`)**.
+    ///
+    /// Same behavior and parameters as [`Scalet::make_cwt_stream_f32`], but for `f64` data.
+    pub fn make_cwt_stream_f64(
+        wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync>,
+        options: CwtStreamOptions,
+    ) -> Result<Box<dyn CwtStreamExecutor<f64> + Send + Sync>, ScaletError> {
+        create_cwt_stream(wavelet, options)
+    }
+
+    /// Computes the scale bounds an executor built with the same arguments would use (f32 version).
+    ///
+    /// Only the wavelet is evaluated, no FFT plans are created.
+    ///
+    /// # Arguments
+    ///
+    /// * `wavelet` – A reference-counted, thread-safe wavelet implementing `CwtWavelet<f32>`.
+    /// * `length` – The maximum length of the signal.
+    /// * `options` – CWT options, only `padding` and `scale_cutoffs` are used.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the smallest and largest scale, or a `ScaletError`
+    /// if `length` is zero, the cutoffs are invalid or the wavelet search fails.
+    pub fn scale_bounds_f32(
+        wavelet: Arc<dyn CwtWavelet<f32> + Send + Sync>,
+        length: usize,
+        options: CwtOptions,
+    ) -> Result<ScaleBounds<f32>, ScaletError> {
+        make_scale_bounds(wavelet, length, options)
+    }
+
+    /// Computes the scale bounds an executor built with the same arguments would use (f64 version).
+    ///
+    /// Same behavior and parameters as [`Scalet::scale_bounds_f32`], but for `f64` data.
+    pub fn scale_bounds_f64(
+        wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync>,
+        length: usize,
+        options: CwtOptions,
+    ) -> Result<ScaleBounds<f64>, ScaletError> {
+        make_scale_bounds(wavelet, length, options)
+    }
+
+    /// Generates the scales an executor built with the same arguments would use (f32 version).
+    ///
+    /// The result is identical to [`CwtExecutor::view_scales`] of
+    /// [`Scalet::make_cwt_f32`], without allocating FFT plans, and can be passed
+    /// to [`Scalet::make_cwt_with_scales_f32`] to share a grid across executors.
+    ///
+    /// # Arguments
+    ///
+    /// * `wavelet` – A reference-counted, thread-safe wavelet implementing `CwtWavelet<f32>`.
+    /// * `length` – The maximum length of the signal.
+    /// * `options` – CWT options controlling the scale type, voices, padding and cutoffs.
+    pub fn make_scales_f32(
+        wavelet: Arc<dyn CwtWavelet<f32> + Send + Sync>,
+        length: usize,
+        options: CwtOptions,
+    ) -> Result<Vec<f32>, ScaletError> {
+        make_length_scales(wavelet, length, options)
+    }
+
+    /// Generates the scales an executor built with the same arguments would use (f64 version).
+    ///
+    /// Same behavior and parameters as [`Scalet::make_scales_f32`], but for `f64` data.
+    pub fn make_scales_f64(
+        wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync>,
+        length: usize,
+        options: CwtOptions,
+    ) -> Result<Vec<f64>, ScaletError> {
+        make_length_scales(wavelet, length, options)
+    }
+
+    /// Generates a scale grid between arbitrary bounds (f32 version).
+    ///
+    /// # Arguments
+    ///
+    /// * `bounds` – Smallest and largest scale, both finite and positive.
+    /// * `scale_type` – Distribution of the scales.
+    /// * `nv` – Voices per octave for [`ScaleType::Log`], total number of scales for
+    ///   [`ScaleType::Linear`]. [`ScaleType::Log`] yields consecutive powers of
+    ///   `2^(1/nv)`, starting at the last one not above `bounds.min` and covering
+    ///   `bounds` to within one voice. [`ScaleType::Linear`] yields `nv` evenly spaced
+    ///   scales from `bounds.min` to `bounds.max`.
+    ///
+    /// # Returns
+    ///
+    /// Scales in ascending order, or a `ScaletError` if the bounds are invalid or `nv` is zero.
+    pub fn scale_grid_f32(
+        bounds: ScaleBounds<f32>,
+        scale_type: ScaleType,
+        nv: usize,
+    ) -> Result<Vec<f32>, ScaletError> {
+        scale_grid(bounds, scale_type, nv)
+    }
+
+    /// Generates a scale grid between arbitrary bounds (f64 version).
+    ///
+    /// Same behavior and parameters as [`Scalet::scale_grid_f32`], but for `f64` data.
+    pub fn scale_grid_f64(
+        bounds: ScaleBounds<f64>,
+        scale_type: ScaleType,
+        nv: usize,
+    ) -> Result<Vec<f64>, ScaletError> {
+        scale_grid(bounds, scale_type, nv)
+    }
+
     /// Converts wavelet scales to corresponding frequencies (f32 version).
     ///
     /// # Arguments
@@ -258,7 +693,7 @@ impl Scalet {
     /// # Returns
     ///
     /// Returns a `Result` containing a `Vec<f32>` of frequencies corresponding to the input scales,
-    /// or a `ScaletError` if the computation fails.
+    /// or a `ScaletError` if `sampling_frequency` is not finite and positive or the computation fails.
     ///
     /// # Behavior
     ///
@@ -271,7 +706,14 @@ impl Scalet {
         filter_length: usize,
         sampling_frequency: f32,
     ) -> Result<Vec<f32>, ScaletError> {
-        scale_to_frequencies_impl(wavelet, scales, sampling_frequency, filter_length)
+        scale_to_frequencies_impl(
+            wavelet,
+            scales,
+            sampling_frequency,
+            filter_length,
+            FrequencyMode::PeakBin,
+            &WaveletSearch::default(),
+        )
     }
 
     /// Converts wavelet scales to corresponding frequencies (f64 version).
@@ -283,7 +725,113 @@ impl Scalet {
         filter_length: usize,
         sampling_frequency: f64,
     ) -> Result<Vec<f64>, ScaletError> {
-        scale_to_frequencies_impl(wavelet, scales, sampling_frequency, filter_length)
+        scale_to_frequencies_impl(
+            wavelet,
+            scales,
+            sampling_frequency,
+            filter_length,
+            FrequencyMode::PeakBin,
+            &WaveletSearch::default(),
+        )
+    }
+
+    /// Converts wavelet scales to frequencies with a selectable conversion (f32 version).
+    ///
+    /// [`Scalet::scales_to_frequencies_f32`] is this function with [`FrequencyMode::PeakBin`].
+    ///
+    /// # Arguments
+    ///
+    /// * `wavelet` - An `Arc` to a type implementing `CwtWavelet<f32>`.
+    /// * `scales` - Slice of wavelet scales.
+    /// * `filter_length` - Length of the wavelet filter, ignored by [`FrequencyMode::Center`].
+    /// * `sampling_frequency` - Sampling frequency of the original signal.
+    /// * `mode` - How a scale is mapped to a frequency.
+    /// * `search` - Tolerances of the wavelet peak search used by [`FrequencyMode::Center`],
+    ///   e.g. the [`CwtOptions::wavelet_search`] the scales were generated with.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the frequency of every scale, in the same order,
+    /// or a `ScaletError` if `sampling_frequency` is not finite and positive or the computation fails.
+    pub fn scales_to_frequencies_with_mode_f32(
+        wavelet: Arc<dyn CwtWavelet<f32> + Send + Sync>,
+        scales: &[f32],
+        filter_length: usize,
+        sampling_frequency: f32,
+        mode: FrequencyMode,
+        search: &WaveletSearch,
+    ) -> Result<Vec<f32>, ScaletError> {
+        scale_to_frequencies_impl(
+            wavelet,
+            scales,
+            sampling_frequency,
+            filter_length,
+            mode,
+            search,
+        )
+    }
+
+    /// Converts wavelet scales to frequencies with a selectable conversion (f64 version).
+    ///
+    /// Same behavior and parameters as [`Scalet::scales_to_frequencies_with_mode_f32`], but for `f64` data.
+    pub fn scales_to_frequencies_with_mode_f64(
+        wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync>,
+        scales: &[f64],
+        filter_length: usize,
+        sampling_frequency: f64,
+        mode: FrequencyMode,
+        search: &WaveletSearch,
+    ) -> Result<Vec<f64>, ScaletError> {
+        scale_to_frequencies_impl(
+            wavelet,
+            scales,
+            sampling_frequency,
+            filter_length,
+            mode,
+            search,
+        )
+    }
+
+    /// Converts frequencies to wavelet scales (f32 version).
+    ///
+    /// Scales follow `a = ω_c * fs / (2π f)`, where `ω_c` is the peak angular frequency of
+    /// the wavelet, or its energy centroid for [`FrequencyMode::Energy`]. This inverts
+    /// [`FrequencyMode::Center`] exactly, and the grid-based modes in the limit of long filters.
+    ///
+    /// # Arguments
+    ///
+    /// * `wavelet` - An `Arc` to a type implementing `CwtWavelet<f32>`.
+    /// * `frequencies` - Frequencies in the units of `sampling_frequency`, all positive.
+    /// * `sampling_frequency` - Sampling frequency of the signal.
+    /// * `mode` - Conversion to invert.
+    /// * `search` - Tolerances of the wavelet peak search, e.g. the
+    ///   [`CwtOptions::wavelet_search`] of the executor the scales are meant for.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the scale of every frequency, in the same order,
+    /// or a `ScaletError` if an input is invalid or the wavelet search fails.
+    pub fn frequencies_to_scales_f32(
+        wavelet: Arc<dyn CwtWavelet<f32> + Send + Sync>,
+        frequencies: &[f32],
+        sampling_frequency: f32,
+        mode: FrequencyMode,
+        search: &WaveletSearch,
+    ) -> Result<Vec<f32>, ScaletError> {
+        frequencies_to_scales_impl(wavelet, frequencies, sampling_frequency, mode, search)
+    }
+
+    /// Converts frequencies to wavelet scales (f64 version).
+    ///
+    /// Same behavior and parameters as [`Scalet::frequencies_to_scales_f32`], but for `f64` data.
+    pub fn frequencies_to_scales_f64(
+        wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync>,
+        frequencies: &[f64],
+        sampling_frequency: f64,
+        mode: FrequencyMode,
+        search: &WaveletSearch,
+    ) -> Result<Vec<f64>, ScaletError> {
+        frequencies_to_scales_impl(wavelet, frequencies, sampling_frequency, mode, search)
     }
 
     /// Draws a colorful scaleogram from CWT coefficients (f32 version).
@@ -0,0 +1,348 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::cwt_executor::CommonCwtExecutor;
+use crate::err::try_vec;
+use crate::freqs::scale_to_frequencies_impl;
+use crate::sample::CwtSample;
+use crate::{CwtExecutor, FrequencyMode, ReconstructionFormula, ScaleBand, ScaletError};
+use num_complex::Complex;
+use num_traits::AsPrimitive;
+
+/// Time–frequency region of a scalogram, see [`CwtAnalysis::masked_inverse`](crate::CwtAnalysis::masked_inverse).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TimeFrequencyMask<'a, T> {
+    /// `[scale][time]` selection, `true` keeps the coefficient.
+    Binary(&'a [Vec<bool>]),
+    /// `[scale][time]` weights every coefficient is multiplied by.
+    Weights(&'a [Vec<T>]),
+    /// Coefficients inside a polygon in the time–frequency plane.
+    Polygon {
+        /// Vertices as `(time, frequency)` in seconds and Hz, at least three.
+        /// Coefficients are placed at the center frequency of their scale.
+        vertices: &'a [(T, T)],
+        /// Sampling frequency of the input in Hz.
+        sampling_frequency: T,
+    },
+}
+
+/// Even-odd rule, points exactly on an edge may fall either way.
+fn polygon_contains<T: CwtSample>(vertices: &[(T, T)], x: T, y: T) -> bool {
+    let mut inside = false;
+    let mut previous = vertices[vertices.len() - 1];
+    for &current in vertices {
+        let ((x0, y0), (x1, y1)) = (previous, current);
+        if (y0 > y) != (y1 > y) && x < (x1 - x0) * (y - y0) / (y1 - y0) + x0 {
+            inside = !inside;
+        }
+        previous = current;
+    }
+    inside
+}
+
+impl<T: CwtSample> CommonCwtExecutor<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    /// Validates the shape of a `[scale][time]` mask against coefficients of `length`.
+    fn check_mask<V>(&self, mask: &[Vec<V>], length: usize) -> Result<(), ScaletError> {
+        if mask.len() != self.scales.len() {
+            return Err(ScaletError::InvalidInputSize(self.scales.len(), mask.len()));
+        }
+        if let Some(row) = mask.iter().find(|x| x.len() != length) {
+            return Err(ScaletError::InvalidInputSize(length, row.len()));
+        }
+        Ok(())
+    }
+
+    /// `[scale][time]` weights of `mask` for coefficients of `length`, inverted when `remove`.
+    fn mask_weights(
+        &self,
+        mask: TimeFrequencyMask<'_, T>,
+        length: usize,
+        remove: bool,
+    ) -> Result<Vec<Vec<T>>, ScaletError> {
+        let one: T = 1f64.as_();
+        let mut weights = try_vec![try_vec![T::zero(); length]; self.scales.len()];
+        match mask {
+            TimeFrequencyMask::Binary(mask) => {
+                self.check_mask(mask, length)?;
+                for (dst, src) in weights.iter_mut().zip(mask.iter()) {
+                    for (dst, &keep) in dst.iter_mut().zip(src.iter()) {
+                        *dst = if keep != remove { one } else { T::zero() };
+                    }
+                }
+            }
+            TimeFrequencyMask::Weights(mask) => {
+                self.check_mask(mask, length)?;
+                for (dst, src) in weights.iter_mut().zip(mask.iter()) {
+                    for (dst, &w) in dst.iter_mut().zip(src.iter()) {
+                        *dst = if remove { one - w } else { w };
+                    }
+                }
+            }
+            TimeFrequencyMask::Polygon {
+                vertices,
+                sampling_frequency,
+            } => {
+                if vertices.len() < 3 {
+                    return Err(ScaletError::Generic(format!(
+                        "Polygon needs at least three vertices, but it had {}",
+                        vertices.len()
+                    )));
+                }
+                let frequencies = scale_to_frequencies_impl(
+                    self.wavelet.clone(),
+                    &self.scales,
+                    sampling_frequency,
+                    self.execution_length,
+                    FrequencyMode::Center,
+                    &self.wavelet_search,
+                )?;
+                let period = one / sampling_frequency;
+                for (dst, &frequency) in weights.iter_mut().zip(frequencies.iter()) {
+                    for (t, dst) in dst.iter_mut().enumerate() {
+                        let time: T = t.as_();
+                        let inside = polygon_contains(vertices, time * period, frequency);
+                        *dst = if inside != remove { one } else { T::zero() };
+                    }
+                }
+            }
+        }
+        Ok(weights)
+    }
+
+    /// Multiplies `coeffs` by `weights` and reconstructs the real signal.
+    fn weighted_inverse(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        weights: &[Vec<T>],
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<T>, ScaletError> {
+        let mut masked = try_vec![Vec::new(); coeffs.len()];
+        for ((dst, row), weights) in masked.iter_mut().zip(coeffs.iter()).zip(weights.iter()) {
+            *dst = try_vec![Complex::new(T::zero(), T::zero()); row.len()];
+            for ((dst, &w), &weight) in dst.iter_mut().zip(row.iter()).zip(weights.iter()) {
+                *dst = w * weight;
+            }
+        }
+        self.inverse(&masked, formula)
+    }
+
+    pub(crate) fn masked_inverse_impl(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        mask: TimeFrequencyMask<'_, T>,
+        remove: bool,
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<T>, ScaletError> {
+        let length = self.check_coeffs(coeffs)?;
+        let weights = self.mask_weights(mask, length, remove)?;
+        self.weighted_inverse(coeffs, &weights, formula)
+    }
+
+    pub(crate) fn band_pass_impl(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        band: ScaleBand,
+        formula: ReconstructionFormula,
+    ) -> Result<Vec<T>, ScaletError> {
+        let length = self.check_coeffs(coeffs)?;
+        let mut weights = try_vec![try_vec![T::zero(); length]; coeffs.len()];
+        for index in self.band_indices(band)? {
+            weights[index].fill(1f64.as_());
+        }
+        self.weighted_inverse(coeffs, &weights, formula)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CwtAnalysis, CwtOptions, CwtWavelet, MorletWavelet, PaddingMode, Scalet};
+    use std::sync::Arc;
+
+    const N: usize = 2048;
+    const SAMPLING_FREQUENCY: f64 = 1000.;
+
+    /// Executor over `cos 20 Hz + 0.5 cos 120 Hz`, the coefficients, and both tones.
+    #[allow(clippy::type_complexity)]
+    fn two_tones() -> Result<
+        (
+            Arc<dyn CwtAnalysis<f64> + Send + Sync>,
+            Vec<Vec<Complex<f64>>>,
+            Vec<f64>,
+            Vec<f64>,
+        ),
+        ScaletError,
+    > {
+        let tone = |amplitude: f64, frequency: f64| {
+            let omega = std::f64::consts::TAU * frequency / SAMPLING_FREQUENCY;
+            (0..N)
+                .map(move |i| amplitude * (omega * i as f64).cos())
+                .collect::<Vec<f64>>()
+        };
+        let low = tone(1., 20.);
+        let high = tone(0.5, 120.);
+        let signal = low
+            .iter()
+            .zip(high.iter())
+            .map(|(a, b)| a + b)
+            .collect::<Vec<f64>>();
+        let wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync> = Arc::new(MorletWavelet::default());
+        let options = CwtOptions {
+            padding: PaddingMode::Reflect,
+            ..Default::default()
+        };
+        let executor = Scalet::make_cwt_f64(wavelet, N, options)?;
+        let coeffs = executor.execute(&signal)?;
+        Ok((executor, coeffs, low, high))
+    }
+
+    /// Root mean square error over `range`.
+    fn rmse(actual: &[f64], expected: &[f64], range: std::ops::Range<usize>) -> f64 {
+        (actual[range.clone()]
+            .iter()
+            .zip(expected[range.clone()].iter())
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f64>()
+            / range.len() as f64)
+            .sqrt()
+    }
+
+    #[test]
+    fn band_pass_isolates_one_of_two_tones() -> Result<(), ScaletError> {
+        let (executor, coeffs, low, high) = two_tones()?;
+        for (min, max, expected) in [(60., 250., &high), (5., 50., &low)] {
+            let band = ScaleBand::Frequencies {
+                min,
+                max,
+                sampling_frequency: SAMPLING_FREQUENCY,
+                mode: FrequencyMode::Center,
+            };
+            for formula in [
+                ReconstructionFormula::SingleIntegral,
+                ReconstructionFormula::DoubleIntegral,
+            ] {
+                let filtered = executor.band_pass(&coeffs, band, formula)?;
+                let rmse = rmse(&filtered, expected, N / 8..7 * N / 8);
+                // The other tone is gone, the kept one restored up to a small fraction.
+                assert!(
+                    rmse < 5e-3,
+                    "band [{min}, {max}] Hz, {formula:?}: rmse {rmse}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn polygon_keeps_or_removes_one_of_two_tones() -> Result<(), ScaletError> {
+        let (executor, coeffs, low, high) = two_tones()?;
+        // Spans the whole record in time and the upper tone in frequency.
+        let vertices = [(-1., 60.), (3., 60.), (3., 250.), (-1., 250.)];
+        let polygon = TimeFrequencyMask::Polygon {
+            vertices: &vertices,
+            sampling_frequency: SAMPLING_FREQUENCY,
+        };
+        let formula = ReconstructionFormula::DoubleIntegral;
+        let interior = N / 8..7 * N / 8;
+        let kept = executor.masked_inverse(&coeffs, polygon, formula)?;
+        assert!(rmse(&kept, &high, interior.clone()) < 5e-3);
+        let removed = executor.remove_region(&coeffs, polygon, formula)?;
+        assert!(rmse(&removed, &low, interior.clone()) < 5e-3);
+        // Keeping and removing a region split the full reconstruction.
+        let full = executor.inverse(&coeffs, formula)?;
+        let sum = kept
+            .iter()
+            .zip(removed.iter())
+            .map(|(a, b)| a + b)
+            .collect::<Vec<f64>>();
+        assert!(rmse(&sum, &full, 0..N) < 1e-12);
+
+        // The same region as a boolean and as a weight matrix.
+        let frequencies = Scalet::scales_to_frequencies_with_mode_f64(
+            Arc::new(MorletWavelet::default()),
+            executor.view_scales(),
+            N,
+            SAMPLING_FREQUENCY,
+            FrequencyMode::Center,
+            &CwtOptions::default().wavelet_search,
+        )?;
+        let binary = frequencies
+            .iter()
+            .map(|&f| vec![(60. ..250.).contains(&f); N])
+            .collect::<Vec<Vec<bool>>>();
+        let weights = binary
+            .iter()
+            .map(|row| row.iter().map(|&keep| f64::from(u8::from(keep))).collect())
+            .collect::<Vec<Vec<f64>>>();
+        for mask in [
+            TimeFrequencyMask::Binary(&binary),
+            TimeFrequencyMask::Weights(&weights),
+        ] {
+            let kept = executor.masked_inverse(&coeffs, mask, formula)?;
+            assert!(rmse(&kept, &high, interior.clone()) < 5e-3);
+            let removed = executor.remove_region(&coeffs, mask, formula)?;
+            assert!(rmse(&removed, &low, interior.clone()) < 5e-3);
+        }
+
+        // A polygon limited in time keeps the tone inside its window only.
+        let window = [(0.8, 60.), (1.3, 60.), (1.3, 250.), (0.8, 250.)];
+        let windowed = executor.masked_inverse(
+            &coeffs,
+            TimeFrequencyMask::Polygon {
+                vertices: &window,
+                sampling_frequency: SAMPLING_FREQUENCY,
+            },
+            formula,
+        )?;
+        let zeros = vec![0f64; N];
+        assert!(rmse(&windowed, &high, 950..1150) < 0.05);
+        assert!(rmse(&windowed, &zeros, 256..600) < 0.01);
+        assert!(rmse(&windowed, &zeros, 1450..1792) < 0.01);
+
+        let triangle = [(0., 60.), (1., 60.)];
+        let degenerate = TimeFrequencyMask::Polygon {
+            vertices: &triangle,
+            sampling_frequency: SAMPLING_FREQUENCY,
+        };
+        assert!(
+            executor
+                .masked_inverse(&coeffs, degenerate, formula)
+                .is_err()
+        );
+        assert!(
+            executor
+                .masked_inverse(&coeffs, TimeFrequencyMask::Binary(&binary[1..]), formula)
+                .is_err()
+        );
+        Ok(())
+    }
+}
@@ -26,6 +26,7 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::neon::NeonSpectrumF32;
 use crate::neon::util::{vfcmul_conj_f32, vfcmulq_conj_f32};
 use crate::spetrum_arith::SpectrumArithmetic;
 use num_complex::Complex;
@@ -35,6 +36,14 @@ use std::arch::aarch64::*;
 pub(crate) struct FcmaSpectrumF32 {}
 
 impl SpectrumArithmetic<f32> for FcmaSpectrumF32 {
+    fn power(&self, dst: &mut [f32], src: &[Complex<f32>]) {
+        NeonSpectrumF32::default().power(dst, src);
+    }
+
+    fn magnitude(&self, dst: &mut [f32], src: &[Complex<f32>]) {
+        NeonSpectrumF32::default().magnitude(dst, src);
+    }
+
     fn mul_by_b_conj_normalize(
         &self,
         dst: &mut [Complex<f32>],
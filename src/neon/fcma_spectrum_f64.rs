@@ -27,6 +27,7 @@
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 
+use crate::neon::NeonSpectrumF64;
 use crate::neon::util::vfcmul_conj_f64;
 use crate::spetrum_arith::SpectrumArithmetic;
 use num_complex::Complex;
@@ -36,6 +37,14 @@ use std::arch::aarch64::*;
 pub(crate) struct FcmaSpectrumF64 {}
 
 impl SpectrumArithmetic<f64> for FcmaSpectrumF64 {
+    fn power(&self, dst: &mut [f64], src: &[Complex<f64>]) {
+        NeonSpectrumF64::default().power(dst, src);
+    }
+
+    fn magnitude(&self, dst: &mut [f64], src: &[Complex<f64>]) {
+        NeonSpectrumF64::default().magnitude(dst, src);
+    }
+
     fn mul_by_b_conj_normalize(
         &self,
         dst: &mut [Complex<f64>],
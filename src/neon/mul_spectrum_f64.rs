@@ -36,6 +36,14 @@ use std::arch::aarch64::*;
 pub(crate) struct NeonSpectrumF64 {}
 
 impl SpectrumArithmetic<f64> for NeonSpectrumF64 {
+    fn power(&self, dst: &mut [f64], src: &[Complex<f64>]) {
+        self.power_impl::<false>(dst, src);
+    }
+
+    fn magnitude(&self, dst: &mut [f64], src: &[Complex<f64>]) {
+        self.power_impl::<true>(dst, src);
+    }
+
     fn mul_by_b_conj_normalize(
         &self,
         dst: &mut [Complex<f64>],
@@ -100,3 +108,28 @@ impl SpectrumArithmetic<f64> for NeonSpectrumF64 {
         }
     }
 }
+
+impl NeonSpectrumF64 {
+    /// |src|², or |src| when `ROOT` is set.
+    #[inline]
+    fn power_impl<const ROOT: bool>(&self, dst: &mut [f64], src: &[Complex<f64>]) {
+        unsafe {
+            for (dst, src) in dst.chunks_exact_mut(2).zip(src.chunks_exact(2)) {
+                let v = vld2q_f64(src.as_ptr().cast());
+                let mut p = vfmaq_f64(vmulq_f64(v.0, v.0), v.1, v.1);
+                if ROOT {
+                    p = vsqrtq_f64(p);
+                }
+                vst1q_f64(dst.as_mut_ptr(), p);
+            }
+
+            let dst_rem = dst.chunks_exact_mut(2).into_remainder();
+            let src_rem = src.chunks_exact(2).remainder();
+
+            for (dst, src) in dst_rem.iter_mut().zip(src_rem.iter()) {
+                let p = src.norm_sqr();
+                *dst = if ROOT { p.sqrt() } else { p };
+            }
+        }
+    }
+}
@@ -35,6 +35,14 @@ use std::arch::aarch64::*;
 pub(crate) struct NeonSpectrumF32 {}
 
 impl SpectrumArithmetic<f32> for NeonSpectrumF32 {
+    fn power(&self, dst: &mut [f32], src: &[Complex<f32>]) {
+        self.power_impl::<false>(dst, src);
+    }
+
+    fn magnitude(&self, dst: &mut [f32], src: &[Complex<f32>]) {
+        self.power_impl::<true>(dst, src);
+    }
+
     fn mul_by_b_conj_normalize(
         &self,
         dst: &mut [Complex<f32>],
@@ -120,3 +128,28 @@ impl SpectrumArithmetic<f32> for NeonSpectrumF32 {
         }
     }
 }
+
+impl NeonSpectrumF32 {
+    /// |src|², or |src| when `ROOT` is set.
+    #[inline]
+    fn power_impl<const ROOT: bool>(&self, dst: &mut [f32], src: &[Complex<f32>]) {
+        unsafe {
+            for (dst, src) in dst.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+                let v = vld2q_f32(src.as_ptr().cast());
+                let mut p = vfmaq_f32(vmulq_f32(v.0, v.0), v.1, v.1);
+                if ROOT {
+                    p = vsqrtq_f32(p);
+                }
+                vst1q_f32(dst.as_mut_ptr(), p);
+            }
+
+            let dst_rem = dst.chunks_exact_mut(4).into_remainder();
+            let src_rem = src.chunks_exact(4).remainder();
+
+            for (dst, src) in dst_rem.iter_mut().zip(src_rem.iter()) {
+                let p = src.norm_sqr();
+                *dst = if ROOT { p.sqrt() } else { p };
+            }
+        }
+    }
+}
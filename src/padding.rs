@@ -0,0 +1,208 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use num_traits::Zero;
+
+/// Strategy used to extend the signal beyond its boundaries before the FFT convolution.
+///
+/// Without padding the FFT-based convolution is circular over the internal FFT length.
+/// That length is rounded up to an FFT-friendly size, and inputs shorter than the executor
+/// length are zero-filled, so the signal is generally followed by zeros: the ends of the
+/// signal only wrap into each other when it exactly fills the FFT length, otherwise the
+/// result is a zero-extended linear convolution. With any other mode the signal is extended
+/// to the next power of two (at least ~1.4x its length), centered, and the result is cropped
+/// back to the original length.
+///
+/// Padding reduces edge effects of the forward transform, but makes
+/// [`CwtExecutor::inverse`](crate::CwtExecutor::inverse) approximate, since the coefficients
+/// of the padded region are not part of the output. Keep [`PaddingMode::None`] when the
+/// signal has to be reconstructed exactly.
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum PaddingMode {
+    /// No extension; circular over the internal FFT length, which may be zero-extended.
+    #[default]
+    None,
+    /// Mirror around the edge samples without repeating them: `d c b | a b c d | c b a`.
+    Reflect,
+    /// Mirror including the edge samples: `c b a | a b c d | d c b`.
+    Symmetric,
+    /// Extend with zeros: `0 0 0 | a b c d | 0 0 0`.
+    Zero,
+    /// Repeat the signal periodically: `b c d | a b c d | a b c`.
+    Periodic,
+    /// Replicate the edge samples: `a a a | a b c d | d d d`.
+    Edge,
+}
+
+/// Padded length for a signal of `length` samples.
+///
+/// Mirrors `p2up` from ssqueezepy: the next power of two of `2 * length` rounded in the log
+/// domain, the original samples are centered inside.
+pub(crate) fn padded_length(length: usize, padding: PaddingMode) -> usize {
+    if padding == PaddingMode::None || length == 0 {
+        return length;
+    }
+    1usize << (1 + (length as f64).log2().round() as u32)
+}
+
+/// Smallest `2^a·3^b·5^c` that is not less than `length`.
+pub(crate) fn fast_fft_length(length: usize) -> usize {
+    if length <= 1 {
+        return length;
+    }
+    let mut best = length.next_power_of_two();
+    let mut p5 = 1usize;
+    while p5 < best {
+        let mut p35 = p5;
+        while p35 < best {
+            // smallest power of two that brings 3^b·5^c above the target
+            let mut candidate = p35;
+            while candidate < length {
+                candidate *= 2;
+            }
+            best = best.min(candidate);
+            p35 *= 3;
+        }
+        p5 *= 5;
+    }
+    best
+}
+
+/// Fills `dst` with `src` placed at `left` and the rest extended according to `padding`.
+pub(crate) fn pad_signal<V: Copy + Zero>(
+    src: &[V],
+    dst: &mut [V],
+    left: usize,
+    padding: PaddingMode,
+) {
+    let n = src.len() as isize;
+    if n == 0 {
+        dst.fill(V::zero());
+        return;
+    }
+    for (j, v) in dst.iter_mut().enumerate() {
+        let p = j as isize - left as isize;
+        if (0..n).contains(&p) {
+            *v = src[p as usize];
+            continue;
+        }
+        let index = match padding {
+            PaddingMode::None | PaddingMode::Zero => None,
+            PaddingMode::Periodic => Some(p.rem_euclid(n)),
+            PaddingMode::Edge => Some(p.clamp(0, n - 1)),
+            PaddingMode::Reflect => {
+                if n == 1 {
+                    Some(0)
+                } else {
+                    let period = 2 * n - 2;
+                    let m = p.rem_euclid(period);
+                    Some(if m >= n { period - m } else { m })
+                }
+            }
+            PaddingMode::Symmetric => {
+                let period = 2 * n;
+                let m = p.rem_euclid(period);
+                Some(if m >= n { period - 1 - m } else { m })
+            }
+        };
+        *v = index.map(|i| src[i as usize]).unwrap_or(V::zero());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn padded(padding: PaddingMode) -> Vec<i32> {
+        let mut dst = vec![-1; 11];
+        pad_signal(&[1, 2, 3, 4], &mut dst, 3, padding);
+        dst
+    }
+
+    #[test]
+    fn pad_signal_extension_patterns() {
+        assert_eq!(padded(PaddingMode::None), [0, 0, 0, 1, 2, 3, 4, 0, 0, 0, 0]);
+        assert_eq!(padded(PaddingMode::Zero), [0, 0, 0, 1, 2, 3, 4, 0, 0, 0, 0]);
+        assert_eq!(
+            padded(PaddingMode::Reflect),
+            [4, 3, 2, 1, 2, 3, 4, 3, 2, 1, 2]
+        );
+        assert_eq!(
+            padded(PaddingMode::Symmetric),
+            [3, 2, 1, 1, 2, 3, 4, 4, 3, 2, 1]
+        );
+        assert_eq!(
+            padded(PaddingMode::Periodic),
+            [2, 3, 4, 1, 2, 3, 4, 1, 2, 3, 4]
+        );
+        assert_eq!(padded(PaddingMode::Edge), [1, 1, 1, 1, 2, 3, 4, 4, 4, 4, 4]);
+    }
+
+    #[test]
+    fn pad_signal_single_sample() {
+        for padding in [
+            PaddingMode::Reflect,
+            PaddingMode::Symmetric,
+            PaddingMode::Periodic,
+            PaddingMode::Edge,
+        ] {
+            let mut dst = [0; 5];
+            pad_signal(&[7], &mut dst, 2, padding);
+            assert_eq!(dst, [7; 5]);
+        }
+    }
+
+    #[test]
+    fn fast_fft_length_is_the_smallest_fast_size() {
+        let is_fast = |mut n: usize| {
+            for p in [2, 3, 5] {
+                while n.is_multiple_of(p) {
+                    n /= p;
+                }
+            }
+            n == 1
+        };
+        for length in 2..3000 {
+            let fast = fast_fft_length(length);
+            assert!(fast >= length && is_fast(fast));
+            assert!((length..fast).all(|n| !is_fast(n)));
+        }
+        assert_eq!(fast_fft_length(1009), 1024);
+        assert_eq!(fast_fft_length(1000), 1000);
+    }
+
+    #[test]
+    fn padded_length_centers_with_room_on_both_sides() {
+        assert_eq!(padded_length(1000, PaddingMode::None), 1000);
+        for length in [3, 100, 1000, 1500, 4096] {
+            let padded = padded_length(length, PaddingMode::Reflect);
+            assert!(padded.is_power_of_two());
+            assert!(padded as f64 >= 1.4 * length as f64);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::cwt_executor::CommonCwtExecutor;
+use crate::err::try_vec;
+use crate::freqs::{check_sampling_frequency, scale_to_frequencies_impl};
+use crate::sample::CwtSample;
+use crate::{FrequencyMode, ScaletError};
+use num_complex::Complex;
+use num_traits::AsPrimitive;
+
+/// Phase of `w` relative to `reference`, in `[-π, π]`, free of wrap-around
+/// as long as the true difference stays within half a turn.
+#[inline]
+fn phase_difference<T: CwtSample>(w: Complex<T>, reference: Complex<T>) -> T {
+    let v = w * reference.conj();
+    v.im.atan2(v.re)
+}
+
+impl<T: CwtSample> CommonCwtExecutor<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    pub(crate) fn instantaneous_phase_impl(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+    ) -> Result<Vec<Vec<T>>, ScaletError> {
+        let length = self.check_coeffs(coeffs)?;
+        let mut phase = try_vec![try_vec![T::zero(); length]; coeffs.len()];
+        for (dst, row) in phase.iter_mut().zip(coeffs.iter()) {
+            dst[0] = row[0].im.atan2(row[0].re);
+            // Accumulating wrapped increments unwraps the phase.
+            for t in 1..length {
+                dst[t] = dst[t - 1] + phase_difference(row[t], row[t - 1]);
+            }
+        }
+        Ok(phase)
+    }
+
+    pub(crate) fn instantaneous_frequency_impl(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        sampling_frequency: T,
+    ) -> Result<Vec<Vec<T>>, ScaletError> {
+        check_sampling_frequency(sampling_frequency)?;
+        let length = self.check_coeffs(coeffs)?;
+        let mut frequency = try_vec![try_vec![T::zero(); length]; coeffs.len()];
+        if length < 2 {
+            return Ok(frequency);
+        }
+        // dφ/dt in radians per sample to Hz.
+        let to_hz = sampling_frequency / T::TWO_PI;
+        let half_to_hz = to_hz * 0.5f64.as_();
+        let last = length - 1;
+        for (dst, row) in frequency.iter_mut().zip(coeffs.iter()) {
+            dst[0] = phase_difference(row[1], row[0]) * to_hz;
+            for t in 1..last {
+                dst[t] = phase_difference(row[t + 1], row[t - 1]) * half_to_hz;
+            }
+            dst[last] = phase_difference(row[last], row[last - 1]) * to_hz;
+        }
+        Ok(frequency)
+    }
+
+    /// Arrival time `t - dφ/dω` of every coefficient, the derivative taken across scales.
+    ///
+    /// Differences are taken in `ln ω`, uniform on a logarithmic scale grid,
+    /// and brought back to `ω` by `dφ/dω = dφ/d(ln ω) / ω`.
+    pub(crate) fn group_delay_impl(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        sampling_frequency: T,
+    ) -> Result<Vec<Vec<T>>, ScaletError> {
+        let length = self.check_coeffs(coeffs)?;
+        let rows = coeffs.len();
+        if rows < 2 {
+            return Err(ScaletError::Generic(
+                "Group delay requires at least two scales".to_string(),
+            ));
+        }
+        let frequencies = scale_to_frequencies_impl(
+            self.wavelet.clone(),
+            &self.scales,
+            sampling_frequency,
+            self.execution_length,
+            FrequencyMode::Center,
+            &self.wavelet_search,
+        )?;
+        let log_omegas = frequencies
+            .iter()
+            .map(|&f| (T::TWO_PI * f).ln())
+            .collect::<Vec<T>>();
+
+        let period = 1f64.as_() / sampling_frequency;
+        let mut delay = try_vec![try_vec![T::zero(); length]; rows];
+        for (index, dst) in delay.iter_mut().enumerate() {
+            let lower = index.saturating_sub(1);
+            let upper = (index + 1).min(rows - 1);
+            let span = log_omegas[upper] - log_omegas[lower];
+            let omega = T::TWO_PI * frequencies[index];
+            let scale = if span != T::zero() {
+                1f64.as_() / (span * omega)
+            } else {
+                T::zero()
+            };
+            for (t, (dst, (&w_upper, &w_lower))) in dst
+                .iter_mut()
+                .zip(coeffs[upper].iter().zip(coeffs[lower].iter()))
+                .enumerate()
+            {
+                let t: T = t.as_();
+                *dst = t * period - phase_difference(w_upper, w_lower) * scale;
+            }
+        }
+        Ok(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CwtOptions, CwtWavelet, MorletWavelet, Scalet};
+    use std::sync::Arc;
+
+    #[test]
+    fn tone_has_a_constant_instantaneous_frequency() -> Result<(), ScaletError> {
+        let n = 1024;
+        let sampling_frequency = 500.;
+        let tone = 37.;
+        let omega = std::f64::consts::TAU * tone / sampling_frequency;
+        let signal = (0..n)
+            .map(|i| (omega * i as f64 + 0.3).cos())
+            .collect::<Vec<f64>>();
+        let wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync> = Arc::new(MorletWavelet::default());
+        let executor = Scalet::make_cwt_f64(wavelet, n, CwtOptions::default())?;
+        let coeffs = executor.execute(&signal)?;
+        let frequency = executor.instantaneous_frequency(&coeffs, sampling_frequency)?;
+        let phase = executor.instantaneous_phase(&coeffs)?;
+        let loudest = coeffs
+            .iter()
+            .map(|row| row.iter().map(|v| v.norm_sqr()).sum::<f64>())
+            .collect::<Vec<f64>>();
+        let max = loudest.iter().fold(0f64, |acc, &v| acc.max(v));
+        // Every row the tone dominates rotates at the tone frequency away from the edges,
+        // where the circular transform joins the ends of the tone.
+        for (index, &energy) in loudest.iter().enumerate() {
+            if energy < 0.01 * max {
+                continue;
+            }
+            for t in n / 4..3 * n / 4 {
+                assert!(
+                    (frequency[index][t] - tone).abs() < 1e-6,
+                    "row {index}, sample {t}: {} Hz",
+                    frequency[index][t]
+                );
+                let step = phase[index][t + 1] - phase[index][t];
+                assert!((step - omega).abs() < 1e-6);
+            }
+        }
+        assert!(executor.instantaneous_frequency(&coeffs, 0.).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn impulse_group_delay_is_its_arrival_time() -> Result<(), ScaletError> {
+        let n = 1024;
+        let arrival = 300;
+        let mut signal = vec![0f64; n];
+        signal[arrival] = 1.;
+        let wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync> = Arc::new(MorletWavelet::default());
+        let executor = Scalet::make_cwt_f64(wavelet, n, CwtOptions::default())?;
+        let coeffs = executor.execute(&signal)?;
+        let delay = executor.group_delay(&coeffs, 1.)?;
+        let scales = executor.view_scales();
+        let mut worst = 0f64;
+        // Interior scales, whose support fits in the signal and whose centre frequency
+        // lies well below Nyquist, near the impulse where the coefficients carry its energy.
+        for (index, &scale) in scales.iter().enumerate() {
+            if !(8. ..=100.).contains(&scale) {
+                continue;
+            }
+            let span = scale as usize;
+            for &value in &delay[index][arrival - span..=arrival + span] {
+                worst = worst.max((value - arrival as f64).abs());
+            }
+        }
+        assert!(worst < 0.05, "worst delay error {worst}");
+        assert!(executor.group_delay(&coeffs, 0.).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,351 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::cwt_executor::CommonCwtExecutor;
+use crate::err::try_vec;
+use crate::freqs::{check_sampling_frequency, scale_to_frequencies_impl};
+use crate::sample::CwtSample;
+use crate::scale_bounds::find_wavelet_peak;
+use crate::{FrequencyMode, ScaletError};
+use num_complex::Complex;
+use num_traits::AsPrimitive;
+
+/// Configuration of [`CwtAnalysis::ridges`](crate::CwtAnalysis::ridges).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RidgeOptions {
+    /// Number of ridges extracted one after another. Default 1.
+    pub count: usize,
+    /// Cost of a jump between scales per squared octave, relative to the log-energy
+    /// the ridge follows. Larger values give smoother ridges. Default 2.
+    pub penalty: f64,
+    /// Half-width in octaves of the band around an extracted ridge that is removed
+    /// before the next ridge is searched. Default 0.5.
+    pub exclusion: f64,
+    /// Sampling frequency of the input in Hz, the unit of the ridge frequencies. Default 1.
+    pub sampling_frequency: f64,
+    /// Conversion of ridge scales to frequencies. Default [`FrequencyMode::Center`].
+    pub frequency_mode: FrequencyMode,
+}
+
+impl Default for RidgeOptions {
+    fn default() -> Self {
+        Self {
+            count: 1,
+            penalty: 2.,
+            exclusion: 0.5,
+            sampling_frequency: 1.,
+            frequency_mode: FrequencyMode::Center,
+        }
+    }
+}
+
+/// A ridge of the scalogram, one value per time sample.
+#[derive(Debug, Clone)]
+pub struct Ridge<T> {
+    /// Scale row of the ridge, an index into [`view_scales`](crate::CwtExecutor::view_scales).
+    pub indices: Vec<usize>,
+    /// Instantaneous frequency in Hz.
+    pub frequencies: Vec<T>,
+    /// Instantaneous amplitude, the amplitude of a real sinusoid that gives the same
+    /// coefficient magnitude at the peak of the wavelet.
+    pub amplitudes: Vec<T>,
+}
+
+/// Cheapest jump into every scale row, `min over from of cost[from] + penalty * (p[to] - p[from])²`
+/// with `p` the log2 scales, solved in linear time as the lower envelope of parabolas
+/// (Felzenszwalb & Huttenlocher).
+struct JumpPenalty<T> {
+    penalty: T,
+    /// Rows sorted by position.
+    order: Vec<usize>,
+    /// Positions in sorted order.
+    positions: Vec<T>,
+    /// Sorted indices of the parabolas on the envelope.
+    hull: Vec<usize>,
+    /// Position where each parabola of the envelope starts to be the lowest.
+    bounds: Vec<T>,
+}
+
+impl<T: CwtSample> JumpPenalty<T>
+where
+    f64: AsPrimitive<T>,
+{
+    fn new(positions: &[T], penalty: T) -> Result<Self, ScaletError> {
+        let mut order = try_vec![0usize; positions.len()];
+        for (i, dst) in order.iter_mut().enumerate() {
+            *dst = i;
+        }
+        order.sort_by(|&a, &b| {
+            positions[a]
+                .partial_cmp(&positions[b])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let sorted = order.iter().map(|&i| positions[i]).collect();
+        Ok(Self {
+            penalty,
+            order,
+            positions: sorted,
+            hull: try_vec![0usize; positions.len()],
+            bounds: try_vec![T::zero(); positions.len() + 1],
+        })
+    }
+
+    fn minimize(&mut self, cost: &[T], dst: &mut [T], from: &mut [u32]) {
+        let n = self.order.len();
+        if n == 0 {
+            return;
+        }
+        let value = |q: usize| cost[self.order[q]];
+        if self.penalty <= T::zero() {
+            // Free jumps, every row continues the cheapest one.
+            let best = (0..n).fold(0, |acc, q| if value(q) < value(acc) { q } else { acc });
+            dst.fill(value(best));
+            from.fill(self.order[best] as u32);
+            return;
+        }
+
+        let p = &self.positions;
+        let parabola = |q: usize| value(q) + self.penalty * p[q] * p[q];
+        let two_penalty = 2f64.as_() * self.penalty;
+        let mut k = 0usize;
+        self.hull[0] = 0;
+        self.bounds[0] = T::NEG_INFINITY;
+        self.bounds[1] = T::INFINITY;
+        for q in 1..n {
+            loop {
+                let r = self.hull[k];
+                if p[q] == p[r] {
+                    // Coinciding parabolas, the lower one dominates everywhere.
+                    if value(q) >= value(r) {
+                        break;
+                    }
+                    if k == 0 {
+                        self.hull[0] = q;
+                        self.bounds[1] = T::INFINITY;
+                        break;
+                    }
+                    k -= 1;
+                    continue;
+                }
+                let s = (parabola(q) - parabola(r)) / (two_penalty * (p[q] - p[r]));
+                if k > 0 && s <= self.bounds[k] {
+                    k -= 1;
+                    continue;
+                }
+                k += 1;
+                self.hull[k] = q;
+                self.bounds[k] = s;
+                self.bounds[k + 1] = T::INFINITY;
+                break;
+            }
+        }
+
+        let mut k = 0usize;
+        for q in 0..n {
+            while self.bounds[k + 1] < p[q] {
+                k += 1;
+            }
+            let r = self.hull[k];
+            let distance = p[q] - p[r];
+            dst[self.order[q]] = self.penalty * distance * distance + value(r);
+            from[self.order[q]] = self.order[r] as u32;
+        }
+    }
+}
+
+impl<T: CwtSample> CommonCwtExecutor<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    pub(crate) fn ridges_impl(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        options: RidgeOptions,
+    ) -> Result<Vec<Ridge<T>>, ScaletError> {
+        for (name, value) in [
+            ("penalty", options.penalty),
+            ("exclusion", options.exclusion),
+        ] {
+            if !(0. ..f64::INFINITY).contains(&value) {
+                return Err(ScaletError::Generic(format!(
+                    "Ridge {name} must be finite and non-negative, but it was {value}"
+                )));
+            }
+        }
+        check_sampling_frequency(options.sampling_frequency)?;
+        let length = self.check_coeffs(coeffs)?;
+        let rows = coeffs.len();
+
+        // Energy relative to its maximum, extracted ridges are zeroed out of it.
+        let mut energy = try_vec![try_vec![T::zero(); length]; rows];
+        let mut max = T::zero();
+        for ((dst, row), &scale) in energy.iter_mut().zip(coeffs.iter()).zip(self.scales.iter()) {
+            self.spectrum_arithmetic.power(dst, row);
+            // Compared in L1 normalization, where equal amplitudes give equal energy at any scale.
+            let amplitude = self.l1_amplitude(scale);
+            let weight = amplitude * amplitude;
+            for v in dst.iter_mut() {
+                *v *= weight;
+            }
+            max = dst.iter().fold(max, |acc, &v| acc.max(v));
+        }
+        if max > T::zero() {
+            let recip = 1f64.as_() / max;
+            for v in energy.iter_mut().flatten() {
+                *v *= recip;
+            }
+        }
+
+        let log2_scales = self.scales.iter().map(|&a| a.log2()).collect::<Vec<T>>();
+        let mut jumps = JumpPenalty::new(&log2_scales, options.penalty.as_())?;
+
+        let frequencies = scale_to_frequencies_impl(
+            self.wavelet.clone(),
+            &self.scales,
+            options.sampling_frequency.as_(),
+            self.execution_length,
+            options.frequency_mode,
+            &self.wavelet_search,
+        )?;
+        // A real sinusoid of amplitude A gives |W| = A |ψ(aω)| / 2 in L1 normalization.
+        let (_, peak) = find_wavelet_peak(self.wavelet.clone(), &self.wavelet_search)?;
+        let amplitude_factor = 2f64.as_() / peak;
+
+        let exclusion: T = options.exclusion.as_();
+        let floor: T = 1e-12f64.as_();
+        let mut ridges = Vec::with_capacity(options.count);
+        let mut cost = try_vec![T::zero(); rows];
+        let mut next_cost = try_vec![T::zero(); rows];
+        let mut back = try_vec![0u32; rows * length];
+        for _ in 0..options.count {
+            // Forward pass: cheapest path ending at every row, following -ln(energy)
+            // plus the penalty of every scale jump.
+            for (dst, row) in cost.iter_mut().zip(energy.iter()) {
+                *dst = -(row[0] + floor).ln();
+            }
+            for t in 1..length {
+                let back = &mut back[t * rows..(t + 1) * rows];
+                jumps.minimize(&cost, &mut next_cost, back);
+                // Only differences matter, keep the accumulated cost bounded.
+                let min = next_cost.iter().fold(T::INFINITY, |acc, &v| acc.min(v));
+                for (dst, row) in next_cost.iter_mut().zip(energy.iter()) {
+                    *dst = *dst - min - (row[t] + floor).ln();
+                }
+                std::mem::swap(&mut cost, &mut next_cost);
+            }
+
+            // Backtrack from the cheapest final row.
+            let mut indices = try_vec![0usize; length];
+            let mut current = cost
+                .iter()
+                .enumerate()
+                .fold(
+                    (0usize, T::INFINITY),
+                    |acc, (i, &c)| if c < acc.1 { (i, c) } else { acc },
+                )
+                .0;
+            for t in (0..length).rev() {
+                indices[t] = current;
+                current = back[t * rows + current] as usize;
+            }
+
+            let mut ridge_frequencies = try_vec![T::zero(); length];
+            let mut amplitudes = try_vec![T::zero(); length];
+            for (t, &index) in indices.iter().enumerate() {
+                ridge_frequencies[t] = frequencies[index];
+                amplitudes[t] = coeffs[index][t].norm_sqr().sqrt()
+                    * self.l1_amplitude(self.scales[index])
+                    * amplitude_factor;
+                // Remove the ridge and its neighborhood for the next search.
+                for (row, &log2_scale) in energy.iter_mut().zip(log2_scales.iter()) {
+                    if (log2_scale - log2_scales[index]).abs() <= exclusion {
+                        row[t] = T::zero();
+                    }
+                }
+            }
+
+            ridges.push(Ridge {
+                indices,
+                frequencies: ridge_frequencies,
+                amplitudes,
+            });
+        }
+        Ok(ridges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CwtOptions, CwtWavelet, MorletWavelet, PaddingMode, Scalet};
+    use std::sync::Arc;
+
+    #[test]
+    fn ridge_tracks_a_linear_chirp() -> Result<(), ScaletError> {
+        let n = 2048;
+        let sampling_frequency = 1000.;
+        let amplitude = 0.8;
+        // 40 Hz rising to 200 Hz over the signal.
+        let (f0, f1) = (40., 200.);
+        let duration = n as f64 / sampling_frequency;
+        let rate = (f1 - f0) / duration;
+        let frequency = |t: f64| f0 + rate * t;
+        let signal = (0..n)
+            .map(|i| {
+                let t = i as f64 / sampling_frequency;
+                amplitude * (std::f64::consts::TAU * (f0 * t + 0.5 * rate * t * t)).cos()
+            })
+            .collect::<Vec<f64>>();
+        let wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync> = Arc::new(MorletWavelet::default());
+        let options = CwtOptions {
+            padding: PaddingMode::Reflect,
+            ..Default::default()
+        };
+        let executor = Scalet::make_cwt_f64(wavelet, n, options)?;
+        let coeffs = executor.execute(&signal)?;
+        let ridges = executor.ridges(
+            &coeffs,
+            RidgeOptions {
+                sampling_frequency,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(ridges.len(), 1);
+        let ridge = &ridges[0];
+        // Within about half a voice of the true frequency, 2^(1/64) with the default 32 voices.
+        for i in n / 8..7 * n / 8 {
+            let expected = frequency(i as f64 / sampling_frequency);
+            let error = (ridge.frequencies[i] - expected).abs() / expected;
+            assert!(error < 0.015, "sample {i}: {} Hz", ridge.frequencies[i]);
+            let error = (ridge.amplitudes[i] - amplitude).abs() / amplitude;
+            assert!(error < 0.1, "sample {i}: amplitude {}", ridge.amplitudes[i]);
+        }
+        Ok(())
+    }
+}
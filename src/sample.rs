@@ -30,13 +30,13 @@ use crate::ScaletError;
 use crate::spetrum_arith::SpectrumArithmeticFactory;
 use num_traits::{AsPrimitive, MulAdd, Num, Zero};
 use pxfm::{
-    f_exp, f_exp2, f_exp2f, f_expf, f_log2, f_log2f, f_pow, f_powf, f_rsqrt, f_rsqrtf, f_sincos,
-    f_sincosf,
+    f_atan2, f_atan2f, f_exp, f_exp2, f_exp2f, f_expf, f_log, f_log2, f_log2f, f_logf, f_pow,
+    f_powf, f_rsqrt, f_rsqrtf, f_sincos, f_sincosf,
 };
 use std::fmt::{Debug, Display};
 use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
 use std::sync::Arc;
-use zaft::{FftDirection, FftExecutor, Zaft};
+use zaft::{FftDirection, FftExecutor, R2CFftExecutor, Zaft};
 
 pub trait CwtSample:
     MulAdd<Self, Output = Self>
@@ -61,6 +61,7 @@ pub trait CwtSample:
     + AsPrimitive<usize>
     + AsPrimitive<isize>
     + AsPrimitive<f32>
+    + AsPrimitive<f64>
     + SpectrumArithmeticFactory
 {
     fn pow(self, other: Self) -> Self;
@@ -68,6 +69,7 @@ pub trait CwtSample:
     fn exp2(self) -> Self;
     fn rsqrt(self) -> Self;
     fn log2(self) -> Self;
+    fn ln(self) -> Self;
     fn ceil(self) -> Self;
     fn floor(self) -> Self;
     fn fract(self) -> Self;
@@ -80,7 +82,13 @@ pub trait CwtSample:
         length: usize,
         fft_direction: FftDirection,
     ) -> Result<Arc<dyn FftExecutor<Self> + Send + Sync>, ScaletError>;
+    fn make_r2c_fft(
+        length: usize,
+    ) -> Result<Arc<dyn R2CFftExecutor<Self> + Send + Sync>, ScaletError>;
     fn sincos(self) -> (Self, Self);
+    /// Four-quadrant arctangent of `self / other`, `self` being the ordinate.
+    fn atan2(self, other: Self) -> Self;
+    const EPSILON: Self;
     const NEG_INFINITY: Self;
     const INFINITY: Self;
     const PI: Self;
@@ -117,6 +125,11 @@ impl CwtSample for f32 {
         f_log2f(self)
     }
 
+    #[inline]
+    fn ln(self) -> Self {
+        f_logf(self)
+    }
+
     #[inline]
     fn ceil(self) -> Self {
         f32::ceil(self)
@@ -162,6 +175,11 @@ impl CwtSample for f32 {
         f_sincosf(self)
     }
 
+    #[inline]
+    fn atan2(self, other: Self) -> Self {
+        f_atan2f(self, other)
+    }
+
     fn make_fft(
         length: usize,
         fft_direction: FftDirection,
@@ -176,6 +194,14 @@ impl CwtSample for f32 {
         }
     }
 
+    fn make_r2c_fft(
+        length: usize,
+    ) -> Result<Arc<dyn R2CFftExecutor<Self> + Send + Sync>, ScaletError> {
+        Zaft::make_r2c_fft_f32(length).map_err(|x| ScaletError::FftError(x.to_string()))
+    }
+
+    const EPSILON: Self = f32::EPSILON;
+
     const INFINITY: Self = f32::INFINITY;
 
     const NEG_INFINITY: Self = f32::NEG_INFINITY;
@@ -244,6 +270,11 @@ impl CwtSample for f64 {
         f_log2(self)
     }
 
+    #[inline]
+    fn ln(self) -> Self {
+        f_log(self)
+    }
+
     #[inline]
     fn ceil(self) -> Self {
         f64::ceil(self)
@@ -289,6 +320,11 @@ impl CwtSample for f64 {
         f_sincos(self)
     }
 
+    #[inline]
+    fn atan2(self, other: Self) -> Self {
+        f_atan2(self, other)
+    }
+
     fn make_fft(
         length: usize,
         fft_direction: FftDirection,
@@ -303,6 +339,14 @@ impl CwtSample for f64 {
         }
     }
 
+    fn make_r2c_fft(
+        length: usize,
+    ) -> Result<Arc<dyn R2CFftExecutor<Self> + Send + Sync>, ScaletError> {
+        Zaft::make_r2c_fft_f64(length).map_err(|x| ScaletError::FftError(x.to_string()))
+    }
+
+    const EPSILON: Self = f64::EPSILON;
+
     const INFINITY: Self = f64::INFINITY;
 
     const NEG_INFINITY: Self = f64::NEG_INFINITY;
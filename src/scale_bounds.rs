@@ -30,7 +30,10 @@ use crate::err::try_vec;
 use crate::mla::fmla;
 use crate::sample::CwtSample;
 use crate::{CwtWavelet, ScaletError};
-use num_traits::AsPrimitive;
+use num_complex::Complex;
+use num_traits::{AsPrimitive, Zero};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 pub(crate) fn linspace<T: CwtSample>(
@@ -71,236 +74,360 @@ where
     Ok(result)
 }
 
-fn linspace_exclusive<T: CwtSample>(start: T, end: T, samples: usize) -> Result<Vec<T>, ScaletError>
-where
-    usize: AsPrimitive<T>,
-{
-    if samples == 0 {
-        return Ok(Vec::new());
+/// Tolerances of the searches over the wavelet's frequency response that locate
+/// its peak and cutoff points.
+///
+/// Equality, ordering and hashing compare the floating-point fields by their bit
+/// patterns, in the total order of [`f64::total_cmp`], so that [`CwtOptions`](crate::CwtOptions)
+/// stays `Eq`, `Ord` and `Hash`.
+#[derive(Debug, Copy, Clone)]
+pub struct WaveletSearch {
+    /// Relative tolerance on the angular frequency at which a refinement stops.
+    ///
+    /// Values finer than the sample precision are clamped to it. Default 1e-10.
+    pub tolerance: f64,
+    /// Refinement iterations after which a search reports that it did not converge.
+    /// Default 200.
+    pub max_iterations: usize,
+    /// Highest angular frequency, at scale 1, the wavelet peak is looked for at.
+    /// Default 1e6.
+    pub max_frequency: f64,
+}
+
+impl Default for WaveletSearch {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-10,
+            max_iterations: 200,
+            max_frequency: 1e6,
+        }
     }
+}
 
-    let step = (end - start) / samples.as_();
-    let mut result = try_vec![T::zero(); samples];
+impl PartialEq for WaveletSearch {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
 
-    for (i, dst) in result.iter_mut().enumerate() {
-        *dst = fmla(i.as_(), step, start);
+impl Eq for WaveletSearch {}
+
+impl PartialOrd for WaveletSearch {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
+}
 
-    Ok(result)
+impl Ord for WaveletSearch {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.tolerance
+            .total_cmp(&other.tolerance)
+            .then(self.max_iterations.cmp(&other.max_iterations))
+            .then(self.max_frequency.total_cmp(&other.max_frequency))
+    }
 }
 
-fn find_maximum<T: CwtSample>(
-    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
-    step_size: T,
-    steps_per_search: usize,
-    step_start: T,
-    step_limit: T,
-    min_value: T,
-) -> Result<(T, T), ScaletError>
-where
-    usize: AsPrimitive<T>,
-{
-    let increment = steps_per_search.as_() * step_size;
+impl Hash for WaveletSearch {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tolerance.to_bits().hash(state);
+        self.max_iterations.hash(state);
+        self.max_frequency.to_bits().hash(state);
+    }
+}
 
-    let mut largest_max = min_value;
-    let mut input_value = step_start;
+/// Number of intervals of the coarse grids that bracket a peak or a crossing.
+const BRACKET_INTERVALS: usize = 1024;
+
+/// Magnitude of the wavelet at every angular frequency of `omegas`.
+fn wavelet_magnitudes<T: CwtSample>(
+    wavelet: &(dyn CwtWavelet<T> + Send + Sync),
+    omegas: &[T],
+) -> Result<Vec<T>, ScaletError> {
+    let mut response = try_vec![Complex::zero(); omegas.len()];
+    wavelet.make_wavelet_into(omegas, &mut response)?;
+    Ok(response
+        .iter()
+        .map(|v| fmla(v.re, v.re, v.im * v.im).sqrt())
+        .collect())
+}
 
-    let mut search_idx = 0usize;
+/// Magnitude of the wavelet at a single angular frequency.
+fn wavelet_magnitude<T: CwtSample>(
+    wavelet: &(dyn CwtWavelet<T> + Send + Sync),
+    omega: T,
+) -> Result<T, ScaletError> {
+    let mut response = [Complex::zero()];
+    wavelet.make_wavelet_into(&[omega], &mut response)?;
+    Ok(fmla(
+        response[0].re,
+        response[0].re,
+        response[0].im * response[0].im,
+    )
+    .sqrt())
+}
 
-    loop {
-        let start = step_start + increment * search_idx.as_();
-        let end = start + increment;
-
-        // linspace(start, end, steps_per_search, endpoint=False)
-        let input_values = linspace_exclusive(start, end, steps_per_search)?;
-
-        // output_values = abs(fn(input_values))
-        let output_values = wavelet
-            .make_wavelet(&input_values)?
-            .into_iter()
-            .map(|v| fmla(v.re, v.re, v.im * v.im).sqrt())
-            .collect::<Vec<_>>();
-
-        if input_values.len() != output_values.len() {
-            return Err(ScaletError::WaveletInvalidSize(
-                input_values.len(),
-                output_values.len(),
-            ));
-        }
+/// Absolute tolerance on an angular frequency of magnitude `x`.
+fn search_tolerance<T: CwtSample>(search: &WaveletSearch, x: T) -> T
+where
+    f64: AsPrimitive<T>,
+{
+    let relative = search.tolerance.as_().max(T::EPSILON * 4f64.as_());
+    relative * x.abs().max(T::EPSILON)
+}
 
-        // find max and argmax
-        let mut output_max = T::NEG_INFINITY;
-        let mut argmax = 0usize;
-        for (i, &v) in output_values.iter().enumerate() {
-            if v > output_max {
-                output_max = v;
-                argmax = i;
-            }
+/// Golden-section search for the maximum of `f` on `[a, b]`.
+fn golden_section_max<T: CwtSample>(
+    f: impl Fn(T) -> Result<T, ScaletError>,
+    mut a: T,
+    mut b: T,
+    search: &WaveletSearch,
+) -> Result<T, ScaletError>
+where
+    f64: AsPrimitive<T>,
+{
+    let inv_phi: T = 0.618033988749894848204586834365638118f64.as_();
+    let mut c = b - (b - a) * inv_phi;
+    let mut d = a + (b - a) * inv_phi;
+    let mut fc = f(c)?;
+    let mut fd = f(d)?;
+    for _ in 0..search.max_iterations {
+        if (b - a).abs() <= search_tolerance(search, a.abs().max(b.abs())) {
+            return Ok((a + b) * 0.5f64.as_());
         }
-
-        if output_max > largest_max {
-            largest_max = output_max;
-            input_value = input_values[argmax];
-        } else if output_max < largest_max {
-            break;
+        if fc == fd {
+            // The maximum of a unimodal function lies between two equal values, this
+            // also keeps narrow peaks whose tails underflow to zero inside the bracket.
+            a = c;
+            b = d;
+            c = b - (b - a) * inv_phi;
+            d = a + (b - a) * inv_phi;
+            fc = f(c)?;
+            fd = f(d)?;
+        } else if fc > fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - (b - a) * inv_phi;
+            fc = f(c)?;
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + (b - a) * inv_phi;
+            fd = f(d)?;
         }
+    }
+    Err(ScaletError::Generic(format!(
+        "Wavelet peak search did not converge on [{a}, {b}] within {} iterations",
+        search.max_iterations
+    )))
+}
 
-        search_idx += 1;
-
-        if *input_values.last().unwrap_or(&T::default()) > step_limit {
-            return Err(ScaletError::Generic(format!(
-                "could not find function maximum with given \
-(step_size, steps_per_search, step_start, step_limit, min_value)=({}, {}, {}, {}, {})",
-                step_size, steps_per_search, step_start, step_limit, min_value
-            )));
+/// Brent's method for the root of `f` bracketed by `[a, b]`, `f(a)` and `f(b)`
+/// must have opposite signs.
+fn brent_root<T: CwtSample>(
+    f: impl Fn(T) -> Result<T, ScaletError>,
+    mut a: T,
+    mut b: T,
+    search: &WaveletSearch,
+) -> Result<T, ScaletError>
+where
+    f64: AsPrimitive<T>,
+{
+    let two: T = 2f64.as_();
+    let half: T = 0.5f64.as_();
+    let mut fa = f(a)?;
+    let mut fb = f(b)?;
+    let mut c = b;
+    let mut fc = fb;
+    let mut d = b - a;
+    let mut e = d;
+    for _ in 0..search.max_iterations {
+        if (fb > T::zero() && fc > T::zero()) || (fb < T::zero() && fc < T::zero()) {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol = search_tolerance(search, b) * half;
+        let xm = (c - b) * half;
+        if xm.abs() <= tol || fb == T::zero() {
+            return Ok(b);
+        }
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            // Inverse quadratic interpolation, or secant when only two points are distinct.
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (two * xm * s, 1f64.as_() - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (two * xm * q * (q - r) - (b - a) * (r - 1f64.as_())),
+                    (q - 1f64.as_()) * (r - 1f64.as_()) * (s - 1f64.as_()),
+                )
+            };
+            if p > T::zero() {
+                q = -q;
+            }
+            p = p.abs();
+            let min1 = 3f64.as_() * xm * q - (tol * q).abs();
+            let min2 = (e * q).abs();
+            if two * p < min1.min(min2) {
+                e = d;
+                d = p / q;
+            } else {
+                d = xm;
+                e = d;
+            }
+        } else {
+            // Bisection.
+            d = xm;
+            e = d;
         }
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol { d } else { tol.copysign(xm) };
+        fb = f(b)?;
     }
-
-    Ok((input_value, largest_max))
+    Err(ScaletError::Generic(format!(
+        "Wavelet cutoff search did not converge near {b} within {} iterations",
+        search.max_iterations
+    )))
 }
 
-fn find_first_occurrence<T: CwtSample>(
+/// Locates the angular frequency where the wavelet magnitude peaks,
+/// returns `(w_peak, peak)`.
+///
+/// A coarse grid over `[0, w_max]`, doubling `w_max` until the peak is enclosed,
+/// brackets the maximum, which is then refined by golden-section search.
+pub(crate) fn find_wavelet_peak<T: CwtSample>(
     wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
-    value: T,
-    step_size: T,
-    steps_per_search: usize,
-    step_start: T,
-    step_limit: T,
+    search: &WaveletSearch,
 ) -> Result<(T, T), ScaletError>
 where
     usize: AsPrimitive<T>,
     f64: AsPrimitive<T>,
 {
-    let increment = steps_per_search.as_() * step_size;
-
-    let mut search_idx = 0usize;
-    let mut step_limit_exceeded = false;
-
-    let mut input_values: Vec<T>;
-    let mut output_values: Vec<T>;
-
-    let idx;
-
+    let max_frequency: T = search.max_frequency.as_();
+    let mut w_max: T = 1f64.as_();
     loop {
-        let start = fmla(increment, search_idx.as_(), step_start);
-        let end = start + increment;
-
-        input_values = linspace_exclusive(start, end, steps_per_search)?;
-
-        // clip to step_limit if exceeded
-        if *input_values.last().unwrap_or(&T::default()) > step_limit {
-            step_limit_exceeded = true;
-            for v in &mut input_values {
-                *v = v.min(step_limit);
+        let omegas = linspace(T::zero(), w_max, BRACKET_INTERVALS + 1)?;
+        let magnitudes = wavelet_magnitudes(wavelet.as_ref(), &omegas)?;
+        let mut peak = T::zero();
+        let mut argmax = 0usize;
+        for (i, &v) in magnitudes.iter().enumerate() {
+            if v > peak {
+                peak = v;
+                argmax = i;
             }
         }
-
-        // output_values = abs(fn(input_values))
-        output_values = wavelet
-            .make_wavelet(&input_values)?
-            .into_iter()
-            .map(|v| fmla(v.re, v.re, v.im * v.im).sqrt())
-            .collect();
-
-        if input_values.len() != output_values.len() {
-            return Err(ScaletError::WaveletInvalidSize(
-                input_values.len(),
-                output_values.len(),
-            ));
-        }
-
-        // mxdiff = max(abs(diff(output_values)))
-        let mut mxdiff: T = 0.0f64.as_();
-        for w in output_values.windows(2) {
-            let d = (w[1] - w[0]).abs();
-            mxdiff = mxdiff.max(d);
-        }
-
-        // check if any |output - value| <= mxdiff
-        let mut found = false;
-        let mut best_idx = 0usize;
-        let mut best_err = T::INFINITY;
-
-        for (i, &v) in output_values.iter().enumerate() {
-            let err = (v - value).abs();
-            if err <= mxdiff && err < best_err {
-                best_err = err;
-                best_idx = i;
-                found = true;
+        // The peak is enclosed once it is inside the grid and the response
+        // has clearly decayed at its right end.
+        let last = magnitudes.len() - 1;
+        if peak > T::zero() && argmax < last && magnitudes[last] <= peak * 0.5f64.as_() {
+            let w_peak = golden_section_max(
+                |w| wavelet_magnitude(wavelet.as_ref(), w),
+                omegas[argmax.saturating_sub(1)],
+                omegas[argmax + 1],
+                search,
+            )?;
+            if w_peak <= T::zero() {
+                return Err(ScaletError::Generic(
+                    "Wavelet magnitude peaks at zero frequency, it is not a band-pass filter"
+                        .to_string(),
+                ));
             }
+            let peak = wavelet_magnitude(wavelet.as_ref(), w_peak)?.max(peak);
+            return Ok((w_peak, peak));
         }
-
-        if found {
-            idx = best_idx;
-            break;
-        }
-
-        search_idx += 1;
-
-        if step_limit_exceeded {
+        if w_max >= max_frequency {
             return Err(ScaletError::Generic(format!(
-                "could not find input value to yield function output value={} \
-with given (step_size, steps_per_search, step_start, step_limit)=({}, {}, {}, {})",
-                value, step_size, steps_per_search, step_start, step_limit
+                "Could not locate the wavelet peak below the angular frequency {max_frequency}, \
+consider raising `WaveletSearch::max_frequency`"
             )));
         }
+        w_max = (w_max * 2f64.as_()).min(max_frequency);
     }
+}
 
-    Ok((input_values[idx], output_values[idx]))
+/// Finds the first angular frequency in `[start, limit]` where the wavelet magnitude
+/// crosses `value`.
+///
+/// A coarse grid brackets the first sign change of `|ψ(ω)| - value`,
+/// which is then refined by Brent's method.
+fn find_first_occurrence<T: CwtSample>(
+    wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
+    value: T,
+    start: T,
+    limit: T,
+    search: &WaveletSearch,
+) -> Result<T, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    let omegas = linspace(start, limit, BRACKET_INTERVALS + 1)?;
+    let magnitudes = wavelet_magnitudes(wavelet.as_ref(), &omegas)?;
+    let crossing = |i: usize| magnitudes[i] - value;
+    for i in 0..omegas.len() {
+        if crossing(i) == T::zero() {
+            return Ok(omegas[i]);
+        }
+        if i + 1 < omegas.len() && (crossing(i) < T::zero()) != (crossing(i + 1) < T::zero()) {
+            return brent_root(
+                |w| Ok(wavelet_magnitude(wavelet.as_ref(), w)? - value),
+                omegas[i],
+                omegas[i + 1],
+                search,
+            );
+        }
+    }
+    Err(ScaletError::Generic(format!(
+        "Wavelet magnitude never crosses {value} on the angular frequencies [{start}, {limit}]"
+    )))
 }
 
 pub(crate) fn find_min_scale<T: CwtSample>(
     wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
     cutoff: T,
+    search: &WaveletSearch,
 ) -> Result<T, ScaletError>
 where
     usize: AsPrimitive<T>,
     f64: AsPrimitive<T>,
 {
-    let (w_peak, peak) = find_maximum(
-        wavelet.clone(),
-        1e-3f64.as_(),
-        10000,
-        T::zero(),
-        1000f64.as_(),
-        -1f64.as_(),
-    )?;
+    let (w_peak, peak) = find_wavelet_peak(wavelet.clone(), search)?;
     let (step_start, step_limit) = if cutoff > T::zero() {
         (w_peak, 10f64.as_() * w_peak)
     } else {
         (T::zero(), w_peak)
     };
-    let (w_cutoff, _) = find_first_occurrence(
+    let w_cutoff = find_first_occurrence(
         wavelet.clone(),
         cutoff.abs() * peak,
-        1e-3f64.as_(),
-        10000,
         step_start,
         step_limit,
+        search,
     )?;
     let min_scale = w_cutoff * T::FRAC_1_PI;
     Ok(min_scale)
 }
 
-fn find_wrap_index<T: CwtSample>(n_divs: &[T]) -> Option<usize>
-where
-    f64: AsPrimitive<T>,
-{
-    n_divs
-        .iter()
-        .map(|x| x.fract()) // n_divs % 1
-        .collect::<Vec<_>>()
-        .windows(2) // diff
-        .enumerate()
-        .find(|(_, w)| w[1] - w[0] < -0.8f64.as_())
-        .map(|(i, _)| i)
-}
-
 pub(crate) fn find_max_scale<T: CwtSample>(
     wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
     n: usize,
     min_cutoff: T,
     max_cutoff: T,
+    search: &WaveletSearch,
 ) -> Result<T, ScaletError>
 where
     usize: AsPrimitive<T>,
@@ -317,15 +444,11 @@ where
             max_cutoff, min_cutoff
         )));
     }
+    if n == 0 {
+        return Err(ScaletError::ZeroBaseSized);
+    }
 
-    let (w_peak, peak) = find_maximum(
-        wavelet.clone(),
-        1e-3f64.as_(),
-        10000,
-        T::zero(),
-        1000f64.as_(),
-        -1f64.as_(),
-    )?;
+    let (w_peak, peak) = find_wavelet_peak(wavelet.clone(), search)?;
 
     // we solve the inverse problem; instead of looking for spacing of xi
     // that'd land symmetrically about psih's peak, we pick such points
@@ -333,74 +456,292 @@ where
     // from left symmetry point to zero an integer number of times
 
     // define all points of wavelet from cutoff to peak, left half
-    let (w_cutoff, _) = find_first_occurrence(
+    let w_cutoff = find_first_occurrence(
         wavelet.clone(),
         min_cutoff * peak,
-        1e-3f64.as_(),
-        10000,
         T::zero(),
         w_peak,
+        search,
     )?;
 
+    // consider every point w_cutoff + j / n (except peak) as candidate cutoff
+    // point, and pick the earliest one that yields integer number of increments
+    // from left point of symmetry to origin. The number of increments
+    // x / (2 * (w_peak - x)) grows monotonically, so instead of sweeping the
+    // whole O(n) grid, jump to the first point past the next integer.
     let step = 1.0f64.as_() / n.as_();
-
-    let mut w_ltp = Vec::new();
-    let mut v = w_cutoff;
-
-    while v < w_peak {
-        w_ltp.push(v);
-        v += step;
+    let two: T = 2f64.as_();
+    let n_divs = |x: T| x / ((w_peak - x) * two);
+    let point = |j: usize| fmla(j.as_(), step, w_cutoff);
+
+    let target = n_divs(w_cutoff).floor() + 1f64.as_();
+    // x / (2 * (w_peak - x)) = k  =>  x = 2k * w_peak / (1 + 2k)
+    let w_target = two * target * w_peak / fmla(two, target, 1f64.as_());
+    let mut j: usize = ((w_target - w_cutoff) / step).ceil().max(T::zero()).as_();
+    while j > 0 && n_divs(point(j - 1)) >= target {
+        j -= 1;
     }
-
-    // consider every point on wavelet(w_ltp) (except peak) as candidate cutoff
-    // point, and pick the earliest one that yields integer number of increments
-    // from left point of symmetry to origin
-    let all_but_last = &w_ltp[..w_ltp.len() - 1];
-    let div_size = all_but_last
-        .iter()
-        .map(|&x| (w_peak - x) * 2f64.as_())
-        .collect::<Vec<T>>(); // doubled so peak is skipped
-    let n_divs = all_but_last
-        .iter()
-        .zip(div_size.iter())
-        .map(|(&a, &b)| a / b)
-        .collect::<Vec<T>>();
-    // diff of modulus; first drop in n_divs is like [.98, .99, 0, .01], so at 0
-    // we've hit an integer, and n_divs grows ~linearly so behavior guaranteed
-    // -.8 arbitrary to be ~1 but <1
-    let idx = find_wrap_index(&n_divs).ok_or(ScaletError::Generic(
-        "Failed to find sufficiently-integer xi divisions; try widening (min_cutoff, max_cutoff)"
-            .to_string(),
-    ))?;
-    // the div to base the scale on (angular bin spacing of scale*xi)
-    let div_scale = div_size[idx + 1];
+    while point(j) < w_peak && n_divs(point(j)) < target {
+        j += 1;
+    }
+    // the div to base the scale on (angular bin spacing of scale*xi); a grid
+    // too coarse to land left of the peak, as for tiny n, uses the exact crossing
+    let w_div = if point(j + 1) < w_peak {
+        point(j)
+    } else {
+        w_target
+    };
+    // the div point may not climb past max_cutoff of the peak, which would
+    // squeeze the largest scale towards the peak; cap it there instead
+    let w_limit =
+        find_first_occurrence(wavelet.clone(), max_cutoff * peak, w_cutoff, w_peak, search)?;
+    let w_div = w_div.min(w_limit);
+    let div_scale = (w_peak - w_div) * two;
 
     // div size of scale=1 (spacing between angular bins at scale=1)
-    let w_1div = T::PI / (n / 2).as_();
+    let w_1div = T::TWO_PI / n.as_();
 
     let max_scale = div_scale / w_1div;
     Ok(max_scale)
 }
 
-#[derive(Debug, Clone, Copy)]
-pub(crate) struct MinMaxScale<T> {
-    pub(crate) min: T,
-    pub(crate) max: T,
+/// Cutoffs that bound the automatically generated scales, as fractions of the
+/// wavelet peak magnitude, following ssqueezepy's `cwt_scalebounds`.
+///
+/// Equality, ordering and hashing compare the cutoffs by their bit patterns, in the
+/// total order of [`f64::total_cmp`], so that [`CwtOptions`](crate::CwtOptions)
+/// stays `Eq`, `Ord` and `Hash`.
+#[derive(Debug, Copy, Clone)]
+pub struct ScaleCutoffs {
+    /// Magnitude of the smallest-scale wavelet at the Nyquist frequency.
+    ///
+    /// A positive value places the peak below Nyquist, with the right tail decayed
+    /// to `cutoff * peak` at Nyquist. A negative value lets the peak pass Nyquist,
+    /// the left side having only reached `|cutoff| * peak` there. Default -0.5.
+    pub cutoff: f64,
+    /// Point on the left side of the largest-scale wavelet, as a fraction of its peak,
+    /// where the DC-side spacing of the frequency bins starts. Default 0.6.
+    pub min_cutoff: f64,
+    /// Highest point on the left side of the largest-scale wavelet, as a fraction of its
+    /// peak, the DC-side spacing may start at. When no point between `min_cutoff` and
+    /// `max_cutoff` divides the axis into whole bins, the spacing starts at `max_cutoff`,
+    /// which keeps the largest scale from collapsing towards the peak. Must be greater
+    /// than `min_cutoff`. Default 0.8.
+    pub max_cutoff: f64,
+}
+
+impl Default for ScaleCutoffs {
+    fn default() -> Self {
+        Self {
+            cutoff: -0.5,
+            min_cutoff: 0.6,
+            max_cutoff: 0.8,
+        }
+    }
 }
 
+impl PartialEq for ScaleCutoffs {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScaleCutoffs {}
+
+impl PartialOrd for ScaleCutoffs {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScaleCutoffs {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cutoff
+            .total_cmp(&other.cutoff)
+            .then(self.min_cutoff.total_cmp(&other.min_cutoff))
+            .then(self.max_cutoff.total_cmp(&other.max_cutoff))
+    }
+}
+
+impl Hash for ScaleCutoffs {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cutoff.to_bits().hash(state);
+        self.min_cutoff.to_bits().hash(state);
+        self.max_cutoff.to_bits().hash(state);
+    }
+}
+
+/// Smallest and largest scale a wavelet is analyzed at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleBounds<T> {
+    /// Smallest scale, the highest analyzed frequency.
+    pub min: T,
+    /// Largest scale, the lowest analyzed frequency.
+    pub max: T,
+}
+
+/// Scale bounds of `wavelet` for an FFT of `n` points.
+///
+/// The maximum scale grows linearly with `n`, so that the lowest analyzed
+/// frequency follows the length of the signal.
 pub(crate) fn find_min_max_scales<T: CwtSample>(
     wavelet: Arc<dyn CwtWavelet<T> + Send + Sync>,
-    cutoff: T,
-) -> Result<MinMaxScale<T>, ScaletError>
+    n: usize,
+    cutoffs: ScaleCutoffs,
+    search: &WaveletSearch,
+) -> Result<ScaleBounds<T>, ScaletError>
 where
     usize: AsPrimitive<T>,
     f64: AsPrimitive<T>,
 {
-    let min_scale = find_min_scale(wavelet.clone(), cutoff)?;
-    const M: usize = 4096;
-    let max_scale = find_max_scale(wavelet.clone(), M, 0.6f64.as_(), 0.8f64.as_())?;
-    Ok(MinMaxScale {
+    if cutoffs.cutoff.is_nan() || cutoffs.cutoff.abs() > 1. || cutoffs.cutoff == 0. {
+        return Err(ScaletError::Generic(format!(
+            "`cutoff` must be non-zero and within [-1, 1] (got {})",
+            cutoffs.cutoff
+        )));
+    }
+    let min_scale = find_min_scale(wavelet.clone(), cutoffs.cutoff.as_(), search)?;
+    let max_scale = find_max_scale(
+        wavelet.clone(),
+        n,
+        cutoffs.min_cutoff.as_(),
+        cutoffs.max_cutoff.as_(),
+        search,
+    )?;
+    Ok(ScaleBounds {
         min: min_scale,
-        max: max_scale,
+        // Very short signals cannot resolve anything below the smallest scale.
+        max: max_scale.max(min_scale),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CwtOptions, GaborWavelet, MorletWavelet, Scalet};
+
+    fn morlet() -> Arc<dyn CwtWavelet<f64> + Send + Sync> {
+        Arc::new(MorletWavelet::new(13.4))
+    }
+
+    #[test]
+    fn max_scale_grows_linearly_with_length() -> Result<(), ScaletError> {
+        let search = WaveletSearch::default();
+        let cutoffs = ScaleCutoffs::default();
+        let short = find_min_max_scales(morlet(), 1024, cutoffs, &search)?;
+        let long = find_min_max_scales(morlet(), 4096, cutoffs, &search)?;
+        assert_eq!(short.min, long.min);
+        assert!(short.min < short.max);
+        let ratio = long.max / short.max;
+        assert!((ratio - 4.).abs() < 0.05, "max scale ratio {ratio}");
+        Ok(())
+    }
+
+    #[test]
+    fn min_scale_follows_the_cutoff() -> Result<(), ScaletError> {
+        let search = WaveletSearch::default();
+        let wavelet = morlet();
+        let (w_peak, peak) = find_wavelet_peak(wavelet.clone(), &search)?;
+        for cutoff in [-0.5, -0.9, 0.5, 0.9] {
+            let cutoffs = ScaleCutoffs {
+                cutoff,
+                ..ScaleCutoffs::default()
+            };
+            let bounds = find_min_max_scales(wavelet.clone(), 1024, cutoffs, &search)?;
+            // The smallest-scale wavelet sampled at Nyquist.
+            let at_nyquist =
+                wavelet_magnitude(wavelet.as_ref(), bounds.min * std::f64::consts::PI)?;
+            assert!(
+                (at_nyquist - cutoff.abs() * peak).abs() < 1e-8 * peak,
+                "cutoff {cutoff}: {at_nyquist}"
+            );
+            // Negative cutoffs let the peak pass Nyquist, positive ones keep it below.
+            let peak_frequency = w_peak / bounds.min;
+            assert_eq!(
+                peak_frequency > std::f64::consts::PI,
+                cutoff < 0.,
+                "cutoff {cutoff}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_cutoffs_are_rejected() {
+        let search = WaveletSearch::default();
+        for cutoffs in [
+            ScaleCutoffs {
+                cutoff: 0.,
+                ..ScaleCutoffs::default()
+            },
+            ScaleCutoffs {
+                cutoff: 1.5,
+                ..ScaleCutoffs::default()
+            },
+            ScaleCutoffs {
+                cutoff: f64::NAN,
+                ..ScaleCutoffs::default()
+            },
+            ScaleCutoffs {
+                min_cutoff: 0.8,
+                max_cutoff: 0.6,
+                ..ScaleCutoffs::default()
+            },
+            ScaleCutoffs {
+                min_cutoff: -0.2,
+                ..ScaleCutoffs::default()
+            },
+        ] {
+            assert!(
+                find_min_max_scales(morlet(), 1024, cutoffs, &search).is_err(),
+                "{cutoffs:?}"
+            );
+        }
+        assert!(find_min_max_scales(morlet(), 0, ScaleCutoffs::default(), &search).is_err());
+    }
+
+    #[test]
+    fn max_cutoff_bounds_the_dc_side_spacing() -> Result<(), ScaletError> {
+        let search = WaveletSearch::default();
+        let wavelet = morlet();
+        let (w_peak, peak) = find_wavelet_peak(wavelet.clone(), &search)?;
+        let mut capped = 0;
+        for n in [64, 100, 256, 1000, 1024, 4096] {
+            let loose = find_max_scale(wavelet.clone(), n, 0.6, 0.8, &search)?;
+            let tight = find_max_scale(wavelet.clone(), n, 0.6, 0.62, &search)?;
+            // The DC-side spacing of the largest scale starts where the left side
+            // of the wavelet lies between the two cutoffs.
+            for (max_cutoff, max_scale) in [(0.8, loose), (0.62, tight)] {
+                let w_div = w_peak - max_scale * std::f64::consts::PI / n as f64;
+                let magnitude = wavelet_magnitude(wavelet.as_ref(), w_div)?;
+                assert!(magnitude >= 0.6 * peak * (1. - 1e-6), "n {n}: {magnitude}");
+                assert!(
+                    magnitude <= max_cutoff * peak * (1. + 1e-6),
+                    "n {n}: {magnitude}"
+                );
+            }
+            // Capping can only move the spacing away from the peak.
+            assert!(tight >= loose * (1. - 1e-12), "n {n}: {tight} < {loose}");
+            if tight > loose * (1. + 1e-6) {
+                capped += 1;
+            }
+        }
+        assert!(capped > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn narrow_high_mu_wavelet_builds_an_executor() -> Result<(), ScaletError> {
+        // Both golden-section probes land in tails that underflow to zero.
+        let wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync> =
+            Arc::new(GaborWavelet::new(50., 2000., 0.));
+        let (w_peak, _) = find_wavelet_peak(wavelet.clone(), &WaveletSearch::default())?;
+        assert!((w_peak - 2000.).abs() < 1e-6, "peak at {w_peak}");
+        let executor = Scalet::make_cwt_f64(wavelet, 1024, CwtOptions::default())?;
+        let scales = executor.view_scales();
+        assert!(!scales.is_empty());
+        assert!(scales.iter().all(|s| s.is_finite() && *s > 0.));
+        assert!(scales.windows(2).all(|w| w[0] < w[1]));
+        Ok(())
+    }
+}
@@ -79,29 +79,38 @@ where
     Ok(base_scales)
 }
 
+/// `count` evenly spaced scales from `min_scale` to `max_scale` (both inclusive).
 pub(crate) fn linear_scales<T: CwtSample>(
     min_scale: T,
     max_scale: T,
-    nv: T,
+    count: usize,
 ) -> Result<Vec<T>, ScaletError>
 where
     usize: AsPrimitive<T>,
-    isize: AsPrimitive<T>,
 {
-    // 1. Calculate na (number of scales needed)
-    // na = int(ceil(nv * log2(max_scale / min_scale)))
-    let na: isize = (nv * (max_scale / min_scale).log2()).ceil().as_();
-
-    // 2. Calculate mn_pow and mx_pow (min and max exponents for the 2^ distribution)
-    // mn_pow = int(floor(nv * log2(min_scale)))
-    let mn_pow: isize = (nv * min_scale.log2()).floor().as_();
-
-    // mx_pow = mn_pow + na
-    let mx_pow = mn_pow + na;
+    linspace(min_scale, max_scale, count)
+}
 
-    // mx_pow = mn_pow + na
-    let min_scale = (mn_pow.as_() / nv).exp2();
-    let max_scale = (mx_pow.as_() / nv).exp2();
-    let na: usize = (max_scale / min_scale).ceil().as_();
-    linspace(min_scale, max_scale, na)
+/// Geometrically spaced samples between `start` and `end` (both inclusive).
+pub(crate) fn geomspace<T: CwtSample>(
+    start: T,
+    end: T,
+    samples: usize,
+) -> Result<Vec<T>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+{
+    let mut result = linspace(start.log2(), end.log2(), samples)?;
+    for v in result.iter_mut() {
+        *v = v.exp2();
+    }
+    if let Some(first) = result.first_mut() {
+        *first = start;
+    }
+    if samples > 1 {
+        if let Some(last) = result.last_mut() {
+            *last = end;
+        }
+    }
+    Ok(result)
 }
@@ -0,0 +1,515 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::ScaletError;
+use crate::cwt_executor::CommonCwtExecutor;
+use crate::err::try_vec;
+use crate::sample::CwtSample;
+use num_complex::Complex;
+use num_traits::{AsPrimitive, Zero};
+
+/// Background noise spectrum the wavelet power is tested against.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum NoiseModel {
+    /// White noise, a flat spectrum with the variance of the input.
+    White,
+    /// Red noise, an AR(1) process whose lag-1 autocorrelation is estimated from the input.
+    Red,
+    /// AR(1) process with the given lag-1 coefficient, in `(-1, 1)`.
+    Ar1(f64),
+}
+
+/// Configuration of a Torrence & Compo significance test.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SignificanceOptions {
+    /// Background spectrum of the null hypothesis. Default [`NoiseModel::Red`].
+    pub noise: NoiseModel,
+    /// Confidence level of the test, in `(0, 1)`. Default 0.95.
+    pub confidence: f64,
+}
+
+impl Default for SignificanceOptions {
+    fn default() -> Self {
+        Self {
+            noise: NoiseModel::Red,
+            confidence: 0.95,
+        }
+    }
+}
+
+/// Result of testing a scalogram against a noise background.
+#[derive(Debug, Clone)]
+pub struct Significance<T> {
+    /// Lag-1 coefficient of the background, zero for white noise.
+    pub alpha: T,
+    /// Variance of the input the background is scaled to.
+    pub variance: T,
+    /// Expected wavelet power `E|W(a, b)|²` of the background at every scale.
+    pub background: Vec<T>,
+    /// Wavelet power at the requested confidence at every scale.
+    pub levels: Vec<T>,
+    /// `|W(a, b)|² / level(a)`, laid out as `[scale][time]`; values above one are significant.
+    pub ratio: Vec<Vec<T>>,
+}
+
+/// Variance of `input` and the lag-1 coefficient of its background, as `(variance, alpha)`.
+fn fit_background<T: CwtSample>(input: &[T], noise: NoiseModel) -> Result<(T, T), ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    if input.is_empty() {
+        return Err(ScaletError::ZeroBaseSized);
+    }
+    let mean = input.iter().fold(T::zero(), |acc, &x| acc + x) / input.len().as_();
+    let mut sum_squares = T::zero();
+    let mut lag_one = T::zero();
+    for (i, &x) in input.iter().enumerate() {
+        let d = x - mean;
+        sum_squares += d * d;
+        if let Some(&next) = input.get(i + 1) {
+            lag_one += d * (next - mean);
+        }
+    }
+    if sum_squares <= T::zero() {
+        return Err(ScaletError::Generic(
+            "Significance testing requires an input with non-zero variance".to_string(),
+        ));
+    }
+    let variance = sum_squares / input.len().as_();
+    let alpha = match noise {
+        NoiseModel::White => T::zero(),
+        NoiseModel::Red => lag_one / sum_squares,
+        NoiseModel::Ar1(alpha) => {
+            if !(alpha > -1. && alpha < 1.) {
+                return Err(ScaletError::Generic(format!(
+                    "AR(1) coefficient must be in (-1, 1), but it was {alpha}"
+                )));
+            }
+            alpha.as_()
+        }
+    };
+    Ok((variance, alpha))
+}
+
+/// Normalized AR(1) spectrum at angular frequency `omega` in radians per sample,
+/// its mean over all frequencies is one.
+#[inline]
+fn ar1_spectrum<T: CwtSample>(alpha: T, omega: T) -> T
+where
+    f64: AsPrimitive<T>,
+{
+    let one: T = 1f64.as_();
+    let two: T = 2f64.as_();
+    (one - alpha * alpha) / (one + alpha * alpha - two * alpha * omega.sincos().1)
+}
+
+fn check_confidence(confidence: f64) -> Result<(), ScaletError> {
+    if !(confidence > 0. && confidence < 1.) {
+        return Err(ScaletError::Generic(format!(
+            "Confidence level must be in (0, 1), but it was {confidence}"
+        )));
+    }
+    Ok(())
+}
+
+impl<T: CwtSample> CommonCwtExecutor<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    /// Expected power the executor produces for unit-variance AR(1) noise with `alpha`,
+    /// at every scale.
+    ///
+    /// For a stationary input with spectrum `S`, `E|W(a, b)|² = c² N Σ S(ω) |ψ(aω)|²`,
+    /// where `c` is the normalization of the scale and `N` the FFT length.
+    pub(crate) fn background_power(&self, alpha: T) -> Result<Vec<T>, ScaletError> {
+        let mut workspace = self.make_workspace()?;
+        let workspace = self.split_workspace(&mut workspace);
+        let mut power = try_vec![T::zero(); self.scales.len()];
+        for (index, dst) in power.iter_mut().enumerate() {
+            let scale = self.scales[index];
+            let filter = self.scale_filter(index, workspace.current_psi, workspace.filter)?;
+            let sum = filter
+                .iter()
+                .zip(self.psi.iter())
+                .fold(T::zero(), |acc, (v, &w)| {
+                    acc + ar1_spectrum(alpha, w) * v.norm_sqr()
+                });
+            let norm = self.normalization(scale);
+            *dst = norm * norm * self.execution_length.as_() * sum;
+        }
+        Ok(power)
+    }
+
+    /// Degrees of freedom of the background power averaged over `samples` consecutive
+    /// coefficients at every scale.
+    ///
+    /// Averaged power of correlated complex Gaussian coefficients is approximated by a scaled
+    /// chi-square with `ν = 2n² / Σᵢⱼ |ρ(i - j)|²`, where `ρ` is the autocorrelation of the
+    /// coefficients, taken from the inverse FFT of `S(ω) |ψ(aω)|²`.
+    fn averaged_dof(&self, alpha: T, samples: &[usize]) -> Result<Vec<f64>, ScaletError> {
+        let mut workspace = self.make_workspace()?;
+        let workspace = self.split_workspace(&mut workspace);
+        let mut dof = try_vec![0f64; self.scales.len()];
+        for (index, dst) in dof.iter_mut().enumerate() {
+            let n = samples[index];
+            if n <= 1 {
+                *dst = 2.;
+                continue;
+            }
+            let filter = self.scale_filter(index, workspace.current_psi, workspace.filter)?;
+            let bins = filter.len();
+            let (spectrum, rest) = workspace.padded_row.split_at_mut(bins);
+            for ((dst, v), &w) in spectrum.iter_mut().zip(filter.iter()).zip(self.psi.iter()) {
+                *dst = Complex::new(ar1_spectrum(alpha, w) * v.norm_sqr(), T::zero());
+            }
+            rest.fill(Complex::zero());
+            self.fft_inverse
+                .execute_with_scratch(workspace.padded_row, workspace.scratch)
+                .map_err(|x| ScaletError::FftError(x.to_string()))?;
+            let zero_lag = workspace.padded_row[0].norm_sqr();
+            if zero_lag <= T::zero() {
+                *dst = 2.;
+                continue;
+            }
+            let lags = n.min(self.execution_length);
+            let mut correlated = n as f64;
+            for tau in 1..lags {
+                let rho: f64 = (workspace.padded_row[tau].norm_sqr() / zero_lag).as_();
+                correlated += 2. * (n - tau) as f64 * rho;
+            }
+            *dst = 2. * (n as f64) * (n as f64) / correlated;
+        }
+        Ok(dof)
+    }
+
+    pub(crate) fn significance_impl(
+        &self,
+        input: &[T],
+        coeffs: &[Vec<Complex<T>>],
+        options: SignificanceOptions,
+    ) -> Result<Significance<T>, ScaletError> {
+        check_confidence(options.confidence)?;
+        self.check_length(input.len())?;
+        if coeffs.len() != self.scales.len() {
+            return Err(ScaletError::InvalidInputSize(
+                self.scales.len(),
+                coeffs.len(),
+            ));
+        }
+        if let Some(row) = coeffs.iter().find(|x| x.len() != input.len()) {
+            return Err(ScaletError::InvalidInputSize(input.len(), row.len()));
+        }
+        let (variance, alpha) = fit_background(input, options.noise)?;
+        let mut background = self.background_power(alpha)?;
+        // Complex coefficients have two degrees of freedom.
+        let factor: T = (chi_square_quantile(options.confidence, 2.)? / 2.).as_();
+        let mut levels = try_vec![T::zero(); background.len()];
+        for (level, expected) in levels.iter_mut().zip(background.iter_mut()) {
+            *expected *= variance;
+            *level = *expected * factor;
+        }
+
+        let mut ratio = try_vec![try_vec![T::zero(); input.len()]; coeffs.len()];
+        for ((dst, row), &level) in ratio.iter_mut().zip(coeffs.iter()).zip(levels.iter()) {
+            self.spectrum_arithmetic.power(dst, row);
+            if level > T::zero() {
+                let recip = 1f64.as_() / level;
+                for v in dst.iter_mut() {
+                    *v *= recip;
+                }
+            }
+        }
+
+        Ok(Significance {
+            alpha,
+            variance,
+            background,
+            levels,
+            ratio,
+        })
+    }
+
+    pub(crate) fn global_significance_impl(
+        &self,
+        input: &[T],
+        samples: Option<&[usize]>,
+        options: SignificanceOptions,
+    ) -> Result<Vec<T>, ScaletError> {
+        check_confidence(options.confidence)?;
+        self.check_length(input.len())?;
+        let samples = match samples {
+            Some(samples) => {
+                if samples.len() != self.scales.len() {
+                    return Err(ScaletError::InvalidInputSize(
+                        self.scales.len(),
+                        samples.len(),
+                    ));
+                }
+                samples.to_vec()
+            }
+            None => try_vec![input.len(); self.scales.len()],
+        };
+        let (variance, alpha) = fit_background(input, options.noise)?;
+        let background = self.background_power(alpha)?;
+        let dof = self.averaged_dof(alpha, &samples)?;
+        let mut levels = try_vec![T::zero(); background.len()];
+        for ((level, &expected), &nu) in levels.iter_mut().zip(background.iter()).zip(dof.iter()) {
+            let factor: T = (chi_square_quantile(options.confidence, nu)? / nu).as_();
+            *level = expected * variance * factor;
+        }
+        Ok(levels)
+    }
+}
+
+/// Natural logarithm of the gamma function, Lanczos approximation (g = 7, n = 9).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_93,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_13,
+        -176.615_029_162_140_59,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_571_6e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula.
+        return (std::f64::consts::PI / (std::f64::consts::PI * x).sin()).ln() - ln_gamma(1. - x);
+    }
+    let x = x - 1.;
+    let mut sum = COEFFICIENTS[0];
+    for (i, &c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += c / (x + i as f64);
+    }
+    let t = x + 7.5;
+    0.5 * (2. * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Regularized lower incomplete gamma function `P(a, x)`.
+fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0. {
+        return 0.;
+    }
+    let prefix = (a * x.ln() - x - ln_gamma(a)).exp();
+    if x < a + 1. {
+        // Series representation.
+        let mut term = 1. / a;
+        let mut sum = term;
+        let mut n = a;
+        for _ in 0..1000 {
+            n += 1.;
+            term *= x / n;
+            sum += term;
+            if term.abs() < sum.abs() * 1e-16 {
+                break;
+            }
+        }
+        (sum * prefix).min(1.)
+    } else {
+        // Continued fraction for Q(a, x), modified Lentz's method.
+        const TINY: f64 = 1e-300;
+        let mut b = x + 1. - a;
+        let mut c = 1. / TINY;
+        let mut d = 1. / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1. / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.).abs() < 1e-16 {
+                break;
+            }
+        }
+        (1. - prefix * h).max(0.)
+    }
+}
+
+/// Value below which a chi-square variable with `dof` degrees of freedom falls
+/// with probability `p`.
+pub(crate) fn chi_square_quantile(p: f64, dof: f64) -> Result<f64, ScaletError> {
+    if !(dof > 0. && dof.is_finite()) {
+        return Err(ScaletError::Generic(format!(
+            "Chi-square degrees of freedom must be positive, but it was {dof}"
+        )));
+    }
+    let cdf = |x: f64| gamma_p(dof * 0.5, x * 0.5);
+    let mut high = dof.max(1.);
+    while cdf(high) < p {
+        high *= 2.;
+    }
+    let mut low = 0.;
+    for _ in 0..200 {
+        let mid = 0.5 * (low + high);
+        if cdf(mid) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+        if high - low <= 1e-12 * high {
+            break;
+        }
+    }
+    Ok(0.5 * (low + high))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CwtOptions, CwtWavelet, MorletWavelet, Scalet};
+    use std::sync::Arc;
+
+    /// Unit-variance Gaussian noise from a fixed-seed generator.
+    fn white_noise(n: usize) -> Vec<f64> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut uniform = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        };
+        (0..n)
+            .map(|_| {
+                let (u, v) = (uniform(), uniform());
+                (-2. * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn white_noise_power_matches_the_background() -> Result<(), ScaletError> {
+        let n = 8192;
+        let wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync> = Arc::new(MorletWavelet::default());
+        let executor = Scalet::make_cwt_f64(wavelet, n, CwtOptions::default())?;
+        let noise = white_noise(n);
+        let coeffs = executor.execute(&noise)?;
+        let options = SignificanceOptions {
+            noise: NoiseModel::White,
+            confidence: 0.95,
+        };
+        let significance = executor.significance(&noise, &coeffs, options)?;
+        let mask = executor.cone_of_influence_mask(n)?;
+        // Pooled over every scale, away from the edges, the power follows the background
+        // and exceeds the 95% level about 5% of the time.
+        let (mut count, mut power, mut exceeding) = (0usize, 0f64, 0usize);
+        for (index, ratios) in significance.ratio.iter().enumerate() {
+            let level = significance.levels[index] / significance.background[index];
+            for (&ratio, &inside) in ratios.iter().zip(mask[index].iter()) {
+                if !inside {
+                    count += 1;
+                    power += ratio * level;
+                    exceeding += (ratio > 1.) as usize;
+                }
+            }
+        }
+        let mean = power / count as f64;
+        let fraction = exceeding as f64 / count as f64;
+        assert!((mean - 1.).abs() < 0.05, "mean power ratio {mean}");
+        assert!(
+            (fraction - 0.05).abs() < 0.01,
+            "exceeding fraction {fraction}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn red_noise_fits_its_coefficient_and_spectrum() -> Result<(), ScaletError> {
+        let n = 32768;
+        let alpha = 0.7;
+        let mut red = white_noise(n);
+        for i in 1..n {
+            red[i] += alpha * red[i - 1];
+        }
+        let (variance, fitted) = fit_background(&red, NoiseModel::Red)?;
+        assert!((fitted - alpha).abs() < 0.02, "fitted alpha {fitted}");
+        let expected_variance = 1. / (1. - alpha * alpha);
+        assert!(
+            (variance / expected_variance - 1.).abs() < 0.05,
+            "variance {variance}"
+        );
+
+        // The normalized spectrum averages to one, and peaks at DC with (1 + α) / (1 - α).
+        let bins = 4096;
+        let mean = (0..bins)
+            .map(|k| ar1_spectrum(alpha, std::f64::consts::TAU * k as f64 / bins as f64))
+            .sum::<f64>()
+            / bins as f64;
+        assert!((mean - 1.).abs() < 1e-12, "mean spectrum {mean}");
+        assert!((ar1_spectrum(alpha, 0.) - (1. + alpha) / (1. - alpha)).abs() < 1e-12);
+
+        // Away from the edges, the power of every scale follows the red background.
+        let wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync> = Arc::new(MorletWavelet::default());
+        let executor = Scalet::make_cwt_f64(wavelet, n, CwtOptions::default())?;
+        let coeffs = executor.execute(&red)?;
+        let options = SignificanceOptions {
+            noise: NoiseModel::Red,
+            confidence: 0.95,
+        };
+        let significance = executor.significance(&red, &coeffs, options)?;
+        assert_eq!(significance.alpha, fitted);
+        let mask = executor.cone_of_influence_mask(n)?;
+        let mut tested = 0;
+        for (index, (row, &scale)) in coeffs.iter().zip(executor.view_scales()).enumerate() {
+            let (count, power) = row
+                .iter()
+                .zip(mask[index].iter())
+                .filter(|&(_, &edge)| !edge)
+                .fold((0usize, 0f64), |(c, p), (v, _)| (c + 1, p + v.norm_sqr()));
+            // Morlet coefficients stay correlated over many scales, the average settles
+            // only with hundreds of scales of coefficients.
+            if (count as f64) < 400. * scale.max(1.) {
+                continue;
+            }
+            let ratio = power / count as f64 / significance.background[index];
+            assert!(
+                (ratio - 1.).abs() < 0.15,
+                "scale {scale}: power ratio {ratio}"
+            );
+            tested += 1;
+        }
+        assert!(
+            tested > coeffs.len() / 3,
+            "{tested} of {} scales tested",
+            coeffs.len()
+        );
+        Ok(())
+    }
+}
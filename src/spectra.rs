@@ -0,0 +1,297 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::cwt_executor::CommonCwtExecutor;
+use crate::err::try_vec;
+use crate::freqs::scale_to_frequencies_impl;
+use crate::icwt::log_scale_weights;
+use crate::mla::fmla;
+use crate::sample::CwtSample;
+use crate::{CwtAnalysis, FrequencyMode, ScaletError};
+use num_complex::Complex;
+use num_traits::AsPrimitive;
+
+/// Band of scales collapsed by [`CwtAnalysis::scale_averaged_power`](crate::CwtAnalysis::scale_averaged_power), bounds are inclusive.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ScaleBand {
+    /// Scales from `min` to `max`.
+    Scales { min: f64, max: f64 },
+    /// Scales whose frequency, in Hz, lies from `min` to `max`.
+    Frequencies {
+        min: f64,
+        max: f64,
+        /// Sampling frequency of the input in Hz.
+        sampling_frequency: f64,
+        /// Scale-to-frequency conversion used to match the scales.
+        mode: FrequencyMode,
+    },
+}
+
+/// Time-averaged wavelet power of every scale.
+#[derive(Debug, Clone)]
+pub struct GlobalSpectrum<T> {
+    /// Mean `|W(a, b)|²` over the averaged samples of each scale, in the normalization
+    /// of the executor, zero when none were averaged.
+    pub power: Vec<T>,
+    /// Number of samples averaged at each scale, ready for
+    /// [`CwtAnalysis::global_significance`](crate::CwtAnalysis::global_significance).
+    pub samples: Vec<usize>,
+}
+
+impl<T: CwtSample> CommonCwtExecutor<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    /// Validates that `coeffs` holds one row of equal length per scale and returns the length.
+    pub(crate) fn check_coeffs(&self, coeffs: &[Vec<Complex<T>>]) -> Result<usize, ScaletError> {
+        if coeffs.len() != self.scales.len() {
+            return Err(ScaletError::InvalidInputSize(
+                self.scales.len(),
+                coeffs.len(),
+            ));
+        }
+        let length = coeffs.first().map(|x| x.len()).unwrap_or(0);
+        self.check_length(length)?;
+        if let Some(row) = coeffs.iter().find(|x| x.len() != length) {
+            return Err(ScaletError::InvalidInputSize(length, row.len()));
+        }
+        Ok(length)
+    }
+
+    /// `[scale][time]` mask of coefficients to skip, `None` when everything is kept.
+    fn excluded(
+        &self,
+        length: usize,
+        exclude_coi: bool,
+    ) -> Result<Option<Vec<Vec<bool>>>, ScaletError> {
+        if exclude_coi {
+            Ok(Some(self.cone_of_influence_mask(length)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Indices of the scales inside `band`.
+    pub(crate) fn band_indices(&self, band: ScaleBand) -> Result<Vec<usize>, ScaletError> {
+        let (min, max, values) = match band {
+            ScaleBand::Scales { min, max } => (min, max, None),
+            ScaleBand::Frequencies {
+                min,
+                max,
+                sampling_frequency,
+                mode,
+            } => {
+                let frequencies = scale_to_frequencies_impl(
+                    self.wavelet.clone(),
+                    &self.scales,
+                    sampling_frequency.as_(),
+                    self.execution_length,
+                    mode,
+                    &self.wavelet_search,
+                )?;
+                (min, max, Some(frequencies))
+            }
+        };
+        if min.is_nan() || max.is_nan() || min > max {
+            return Err(ScaletError::Generic(format!(
+                "Band bounds must be ordered, but they were {min} and {max}"
+            )));
+        }
+        let (min, max): (T, T) = (min.as_(), max.as_());
+        let values = values.as_deref().unwrap_or(&self.scales);
+        let mut indices = Vec::new();
+        for (index, &v) in values.iter().enumerate() {
+            if v >= min && v <= max {
+                indices.push(index);
+            }
+        }
+        if indices.is_empty() {
+            return Err(ScaletError::Generic(
+                "Band does not contain any scale of the executor".to_string(),
+            ));
+        }
+        Ok(indices)
+    }
+
+    pub(crate) fn global_wavelet_spectrum_impl(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        exclude_coi: bool,
+    ) -> Result<GlobalSpectrum<T>, ScaletError> {
+        let length = self.check_coeffs(coeffs)?;
+        let excluded = self.excluded(length, exclude_coi)?;
+        let mut power = try_vec![T::zero(); coeffs.len()];
+        let mut samples = try_vec![length; coeffs.len()];
+        let mut row_power = try_vec![T::zero(); length];
+        for (index, row) in coeffs.iter().enumerate() {
+            self.spectrum_arithmetic.power(&mut row_power, row);
+            let mut sum = T::zero();
+            match &excluded {
+                Some(mask) => {
+                    let mut count = 0usize;
+                    for (&v, &skip) in row_power.iter().zip(mask[index].iter()) {
+                        if !skip {
+                            sum += v;
+                            count += 1;
+                        }
+                    }
+                    samples[index] = count;
+                }
+                None => {
+                    sum = row_power.iter().fold(T::zero(), |acc, &v| acc + v);
+                }
+            }
+            if samples[index] > 0 {
+                power[index] = sum / samples[index].as_();
+            }
+        }
+        Ok(GlobalSpectrum { power, samples })
+    }
+
+    /// Scale-averaged power `1/C Σ |W(a, b)|² d(ln a)` of the L1 normalized coefficients,
+    /// the double-integral reconstruction taken on the power instead of the coefficients.
+    pub(crate) fn scale_averaged_power_impl(
+        &self,
+        coeffs: &[Vec<Complex<T>>],
+        band: ScaleBand,
+        exclude_coi: bool,
+    ) -> Result<Vec<T>, ScaletError> {
+        let length = self.check_coeffs(coeffs)?;
+        let indices = self.band_indices(band)?;
+        let excluded = self.excluded(length, exclude_coi)?;
+        let weights = log_scale_weights(&self.scales)?;
+        let c = self.double_integral_constant()?;
+        let mut averaged = try_vec![T::zero(); length];
+        let mut row_power = try_vec![T::zero(); length];
+        for index in indices {
+            self.spectrum_arithmetic
+                .power(&mut row_power, &coeffs[index]);
+            let amplitude = self.l1_amplitude(self.scales[index]);
+            let f = weights[index] * amplitude * amplitude / c;
+            match &excluded {
+                Some(mask) => {
+                    for ((dst, &v), &skip) in averaged
+                        .iter_mut()
+                        .zip(row_power.iter())
+                        .zip(mask[index].iter())
+                    {
+                        if !skip {
+                            *dst = fmla(v, f, *dst);
+                        }
+                    }
+                }
+                None => {
+                    for (dst, &v) in averaged.iter_mut().zip(row_power.iter()) {
+                        *dst = fmla(v, f, *dst);
+                    }
+                }
+            }
+        }
+        Ok(averaged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CwtOptions, CwtWavelet, MorletWavelet, Scalet, SignificanceOptions};
+    use std::sync::Arc;
+
+    fn analysis(
+        n: usize,
+        l1_norm: bool,
+    ) -> Result<Arc<dyn CwtAnalysis<f64> + Send + Sync>, ScaletError> {
+        let wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync> = Arc::new(MorletWavelet::default());
+        Scalet::make_cwt_f64(
+            wavelet,
+            n,
+            CwtOptions {
+                l1_norm,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn tone(n: usize, amplitude: f64, frequency: f64) -> Vec<f64> {
+        (0..n)
+            .map(|i| amplitude * (std::f64::consts::TAU * frequency * i as f64).cos())
+            .collect()
+    }
+
+    #[test]
+    fn global_spectrum_follows_the_executor_normalization() -> Result<(), ScaletError> {
+        let n = 1024;
+        let signal = tone(n, 1.5, 0.05);
+        let l1 = analysis(n, true)?;
+        let l2 = analysis(n, false)?;
+        let l1_coeffs = l1.execute(&signal)?;
+        let l1_spectrum = l1.global_wavelet_spectrum(&l1_coeffs, false)?;
+        let l2_spectrum = l2.global_wavelet_spectrum(&l2.execute(&signal)?, false)?;
+        for (index, row) in l1_coeffs.iter().enumerate() {
+            let mean = row.iter().map(|v| v.norm_sqr()).sum::<f64>() / n as f64;
+            assert!((l1_spectrum.power[index] - mean).abs() <= 1e-12 * mean.max(1.));
+            let scale = l1.view_scales()[index];
+            let converted = l2_spectrum.power[index] * scale;
+            assert!((converted - mean).abs() <= 1e-9 * mean.max(1.));
+        }
+        assert!(l1_spectrum.samples.iter().all(|&s| s == n));
+        // Significance levels share the normalization of the spectrum.
+        let options = SignificanceOptions::default();
+        let l1_levels = l1.global_significance(&signal, None, options)?;
+        let l2_levels = l2.global_significance(&signal, None, options)?;
+        for ((&l1_level, &l2_level), &scale) in
+            l1_levels.iter().zip(l2_levels.iter()).zip(l1.view_scales())
+        {
+            assert!((l2_level * scale - l1_level).abs() <= 1e-9 * l1_level);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn scale_averaged_power_of_a_tone_is_a_quarter_of_its_squared_amplitude()
+    -> Result<(), ScaletError> {
+        let n = 1024;
+        let signal = tone(n, 1.5, 0.05);
+        for l1_norm in [true, false] {
+            let executor = analysis(n, l1_norm)?;
+            let coeffs = executor.execute(&signal)?;
+            let band = ScaleBand::Frequencies {
+                min: 0.,
+                max: 0.5,
+                sampling_frequency: 1.,
+                mode: FrequencyMode::Peak,
+            };
+            let power = executor.scale_averaged_power(&coeffs, band, false)?;
+            for &v in &power[n / 4..3 * n / 4] {
+                assert!((v - 1.5 * 1.5 / 4.).abs() < 1e-3, "l1_norm {l1_norm}: {v}");
+            }
+        }
+        Ok(())
+    }
+}
@@ -49,6 +49,10 @@ pub trait SpectrumArithmetic<T> {
         other: &[Complex<T>],
         normalize_value: T,
     );
+    /// Writes the squared magnitude `|src|²` of every value into `dst`.
+    fn power(&self, dst: &mut [T], src: &[Complex<T>]);
+    /// Writes the magnitude `|src|` of every value into `dst`.
+    fn magnitude(&self, dst: &mut [T], src: &[Complex<T>]);
 }
 
 #[allow(unused)]
@@ -70,6 +74,18 @@ impl<T: CwtSample> SpectrumArithmetic<T> for CommonSpectrumArithmetic<T> {
             *v_dst = c_mul_fast_conj(signal, wavelet) * normalize_value;
         }
     }
+
+    fn power(&self, dst: &mut [T], src: &[Complex<T>]) {
+        for (v_dst, &v) in dst.iter_mut().zip(src.iter()) {
+            *v_dst = fmla(v.re, v.re, v.im * v.im);
+        }
+    }
+
+    fn magnitude(&self, dst: &mut [T], src: &[Complex<T>]) {
+        for (v_dst, &v) in dst.iter_mut().zip(src.iter()) {
+            *v_dst = fmla(v.re, v.re, v.im * v.im).sqrt();
+        }
+    }
 }
 
 pub trait SpectrumArithmeticFactory {
@@ -153,3 +169,126 @@ impl SpectrumArithmeticFactory for f64 {
         .clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Backends<T> = Vec<Arc<dyn SpectrumArithmetic<T> + Send + Sync>>;
+
+    /// Every implementation the host can run, the scalar one first.
+    fn backends_f32() -> Backends<f32> {
+        let mut backends: Backends<f32> = vec![Arc::new(CommonSpectrumArithmetic::default())];
+        #[cfg(all(target_arch = "x86_64", feature = "avx"))]
+        if std::arch::is_x86_feature_detected!("avx2") && std::arch::is_x86_feature_detected!("fma")
+        {
+            backends.push(Arc::new(crate::avx::AvxSpectrumF32::default()));
+        }
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "sse"))]
+        if std::arch::is_x86_feature_detected!("sse4.2") {
+            backends.push(Arc::new(crate::sse::Sse42SpectrumF32::default()));
+        }
+        #[cfg(all(target_arch = "aarch64", feature = "neon"))]
+        {
+            #[cfg(feature = "fcma")]
+            if std::arch::is_aarch64_feature_detected!("fcma") {
+                backends.push(Arc::new(crate::neon::FcmaSpectrumF32::default()));
+            }
+            backends.push(Arc::new(crate::neon::NeonSpectrumF32::default()));
+        }
+        backends.push(f32::spectrum_arithmetic());
+        backends
+    }
+
+    fn backends_f64() -> Backends<f64> {
+        let mut backends: Backends<f64> = vec![Arc::new(CommonSpectrumArithmetic::default())];
+        #[cfg(all(target_arch = "x86_64", feature = "avx"))]
+        if std::arch::is_x86_feature_detected!("avx2") && std::arch::is_x86_feature_detected!("fma")
+        {
+            backends.push(Arc::new(crate::avx::AvxSpectrumF64::default()));
+        }
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "sse"))]
+        if std::arch::is_x86_feature_detected!("sse4.2") {
+            backends.push(Arc::new(crate::sse::Sse42SpectrumF64::default()));
+        }
+        #[cfg(all(target_arch = "aarch64", feature = "neon"))]
+        {
+            #[cfg(feature = "fcma")]
+            if std::arch::is_aarch64_feature_detected!("fcma") {
+                backends.push(Arc::new(crate::neon::FcmaSpectrumF64::default()));
+            }
+            backends.push(Arc::new(crate::neon::NeonSpectrumF64::default()));
+        }
+        backends.push(f64::spectrum_arithmetic());
+        backends
+    }
+
+    /// Distinct values, so any mixed up lane shows, with a length covering the vector
+    /// loops and their remainders.
+    fn values(n: usize, seed: f64) -> Vec<Complex<f64>> {
+        (0..n)
+            .map(|i| {
+                let i = i as f64 + seed;
+                Complex::new(i * 0.37 - 3., (i * 1.3).sin() * (i + 2.))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn power_and_magnitude_keep_lane_order() {
+        let src = values(43, 0.);
+        for backend in backends_f64() {
+            let mut power = vec![0.; src.len()];
+            let mut magnitude = vec![0.; src.len()];
+            backend.power(&mut power, &src);
+            backend.magnitude(&mut magnitude, &src);
+            for ((&p, &m), v) in power.iter().zip(magnitude.iter()).zip(src.iter()) {
+                assert!((p - v.norm_sqr()).abs() <= 1e-12 * v.norm_sqr());
+                assert!((m - v.norm()).abs() <= 1e-12 * v.norm());
+            }
+        }
+        let src = src
+            .iter()
+            .map(|x| Complex::new(x.re as f32, x.im as f32))
+            .collect::<Vec<_>>();
+        for backend in backends_f32() {
+            let mut power = vec![0.; src.len()];
+            let mut magnitude = vec![0.; src.len()];
+            backend.power(&mut power, &src);
+            backend.magnitude(&mut magnitude, &src);
+            for ((&p, &m), v) in power.iter().zip(magnitude.iter()).zip(src.iter()) {
+                assert!((p - v.norm_sqr()).abs() <= 1e-5 * v.norm_sqr());
+                assert!((m - v.norm()).abs() <= 1e-5 * v.norm());
+            }
+        }
+    }
+
+    #[test]
+    fn conjugate_product_matches_scalar() {
+        let input = values(43, 0.);
+        let other = values(43, 17.5);
+        for backend in backends_f64() {
+            let mut dst = vec![Complex::default(); input.len()];
+            backend.mul_by_b_conj_normalize(&mut dst, &input, &other, 0.25);
+            for ((&d, &a), &b) in dst.iter().zip(input.iter()).zip(other.iter()) {
+                let expected = a * b.conj() * 0.25;
+                // Relative to the operands, the product may cancel.
+                assert!((d - expected).norm() <= 1e-12 * 0.25 * a.norm() * b.norm());
+            }
+        }
+        let narrow = |x: &[Complex<f64>]| {
+            x.iter()
+                .map(|x| Complex::new(x.re as f32, x.im as f32))
+                .collect::<Vec<_>>()
+        };
+        let (input, other) = (narrow(&input), narrow(&other));
+        for backend in backends_f32() {
+            let mut dst = vec![Complex::default(); input.len()];
+            backend.mul_by_b_conj_normalize(&mut dst, &input, &other, 0.25);
+            for ((&d, &a), &b) in dst.iter().zip(input.iter()).zip(other.iter()) {
+                let expected = a * b.conj() * 0.25;
+                assert!((d - expected).norm() <= 1e-5 * 0.25 * a.norm() * b.norm());
+            }
+        }
+    }
+}
@@ -57,6 +57,14 @@ impl SpectrumArithmetic<f32> for Sse42SpectrumF32 {
     ) {
         unsafe { self.mul_by_b_conj_normalize_impl(dst, input, other, normalize_value) }
     }
+
+    fn power(&self, dst: &mut [f32], src: &[Complex<f32>]) {
+        unsafe { self.power_impl::<false>(dst, src) }
+    }
+
+    fn magnitude(&self, dst: &mut [f32], src: &[Complex<f32>]) {
+        unsafe { self.power_impl::<true>(dst, src) }
+    }
 }
 
 impl Sse42SpectrumF32 {
@@ -143,4 +151,30 @@ impl Sse42SpectrumF32 {
             }
         }
     }
+
+    /// |src|², or |src| when `ROOT` is set.
+    #[target_feature(enable = "sse4.2")]
+    fn power_impl<const ROOT: bool>(&self, dst: &mut [f32], src: &[Complex<f32>]) {
+        unsafe {
+            for (dst, src) in dst.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+                let v0 = _mm_loadu_ps(src.as_ptr().cast());
+                let v1 = _mm_loadu_ps(src.get_unchecked(2..).as_ptr().cast());
+
+                let mut p = _mm_hadd_ps(_mm_mul_ps(v0, v0), _mm_mul_ps(v1, v1));
+                if ROOT {
+                    p = _mm_sqrt_ps(p);
+                }
+
+                _mm_storeu_ps(dst.as_mut_ptr(), p);
+            }
+
+            let dst_rem = dst.chunks_exact_mut(4).into_remainder();
+            let src_rem = src.chunks_exact(4).remainder();
+
+            for (dst, src) in dst_rem.iter_mut().zip(src_rem.iter()) {
+                let p = src.norm_sqr();
+                *dst = if ROOT { p.sqrt() } else { p };
+            }
+        }
+    }
 }
@@ -48,6 +48,14 @@ impl SpectrumArithmetic<f64> for Sse42SpectrumF64 {
             self.mul_by_b_conj_normalize(dst, input, other, normalize_value);
         }
     }
+
+    fn power(&self, dst: &mut [f64], src: &[Complex<f64>]) {
+        unsafe { self.power_impl::<false>(dst, src) }
+    }
+
+    fn magnitude(&self, dst: &mut [f64], src: &[Complex<f64>]) {
+        unsafe { self.power_impl::<true>(dst, src) }
+    }
 }
 
 #[inline]
@@ -88,4 +96,30 @@ impl Sse42SpectrumF64 {
             }
         }
     }
+
+    /// |src|², or |src| when `ROOT` is set.
+    #[target_feature(enable = "sse4.2")]
+    fn power_impl<const ROOT: bool>(&self, dst: &mut [f64], src: &[Complex<f64>]) {
+        unsafe {
+            for (dst, src) in dst.chunks_exact_mut(2).zip(src.chunks_exact(2)) {
+                let v0 = _mm_loadu_pd(src.as_ptr().cast());
+                let v1 = _mm_loadu_pd(src.get_unchecked(1..).as_ptr().cast());
+
+                let mut p = _mm_hadd_pd(_mm_mul_pd(v0, v0), _mm_mul_pd(v1, v1));
+                if ROOT {
+                    p = _mm_sqrt_pd(p);
+                }
+
+                _mm_storeu_pd(dst.as_mut_ptr(), p);
+            }
+
+            let dst_rem = dst.chunks_exact_mut(2).into_remainder();
+            let src_rem = src.chunks_exact(2).remainder();
+
+            for (dst, src) in dst_rem.iter_mut().zip(src_rem.iter()) {
+                let p = src.norm_sqr();
+                *dst = if ROOT { p.sqrt() } else { p };
+            }
+        }
+    }
 }
@@ -0,0 +1,511 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::cwt_executor::CommonCwtExecutor;
+use crate::err::try_vec;
+use crate::freqs::check_sampling_frequency;
+use crate::icwt::log_scale_weights;
+use crate::mla::fmla;
+use crate::sample::CwtSample;
+use crate::scale_bounds::find_wavelet_peak;
+use crate::scale_bounds::linspace;
+use crate::scales::geomspace;
+use crate::{CwtExecutor, CwtOptions, ScaleType, ScaletError};
+use num_complex::Complex;
+use num_traits::{AsPrimitive, Zero};
+
+/// Configuration options for the Synchrosqueezed Continuous Wavelet Transform (SSQ-CWT).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SsqOptions {
+    /// Options of the underlying CWT.
+    pub cwt: CwtOptions,
+    /// Distribution of the output frequency grid.
+    ///
+    /// - [`ScaleType::Log`]: geometric spacing, matching a logarithmic scale grid.
+    /// - [`ScaleType::Linear`]: uniform spacing in Hz.
+    ///
+    /// The grid always has as many bins as the CWT has scales.
+    pub frequency_scale: ScaleType,
+    /// Absolute magnitude threshold below which CWT coefficients are considered
+    /// too small to carry a reliable phase and are not reassigned, finite and non-negative.
+    pub gamma: f64,
+    /// Sampling frequency of the input, used to express output frequencies in Hz,
+    /// finite and positive.
+    pub sampling_frequency: f64,
+}
+
+impl Default for SsqOptions {
+    fn default() -> Self {
+        Self {
+            cwt: CwtOptions::default(),
+            frequency_scale: ScaleType::Log,
+            // 10 machine epsilons of f32
+            gamma: 10. * f32::EPSILON as f64,
+            sampling_frequency: 1.,
+        }
+    }
+}
+
+/// Output of the Synchrosqueezed CWT.
+#[derive(Debug, Clone)]
+pub struct SsqCwt<T> {
+    /// Plain CWT coefficients, `[num_scales, input_length]`.
+    pub wx: Vec<Vec<Complex<T>>>,
+    /// Synchrosqueezed coefficients, `[num_frequencies, input_length]`.
+    ///
+    /// Rows follow [`frequencies`](Self::frequencies), high frequencies first,
+    /// the same orientation as the rows of `wx`.
+    pub tx: Vec<Vec<Complex<T>>>,
+    /// Frequencies of the `tx` rows in Hz, in **descending** order.
+    pub frequencies: Vec<T>,
+}
+
+/// Executes the Synchrosqueezed Continuous Wavelet Transform.
+///
+/// Synchrosqueezing computes the phase transform `ω(a, b) = Im(∂ₜWx / Wx) / 2π` from the
+/// CWT and its time derivative, then reassigns every coefficient onto the frequency bin
+/// of its instantaneous frequency. Energy of tonal components smeared across many scales
+/// collapses onto a thin ridge.
+pub trait SsqExecutor<T> {
+    /// Executes the SSQ-CWT on a real-valued input signal.
+    ///
+    /// The input may be of any length up to [`length`](Self::length), the outputs have
+    /// as many columns as the input has samples.
+    ///
+    /// Reassignment moves coefficients of every scale onto arbitrary frequency rows,
+    /// so the transform runs on the calling thread and [`CwtOptions::thread_count`]
+    /// is ignored.
+    ///
+    /// # Errors
+    /// Returns `ScaletError` if the input length is incompatible with the
+    /// executor configuration or if an internal FFT operation fails.
+    fn execute(&self, input: &[T]) -> Result<SsqCwt<T>, ScaletError>;
+    /// Returns the maximum length of the input signal this executor was built for.
+    fn length(&self) -> usize;
+    /// Provides a zero-copy view of the CWT scales.
+    fn view_scales(&self) -> &[T];
+    /// Provides a zero-copy view of the output frequency grid in Hz, in descending order.
+    fn view_frequencies(&self) -> &[T];
+    /// Returns the memory, in bytes, taken by the precomputed wavelet filters,
+    /// see [`CwtExecutor::filter_bank_size`](crate::CwtExecutor::filter_bank_size).
+    fn filter_bank_size(&self) -> usize;
+}
+
+pub(crate) struct SsqCwtExecutor<T> {
+    cwt: CommonCwtExecutor<T>,
+    frequencies: Vec<T>,
+    /// Reassignment weight of each scale, d(ln a) in L1 normalization.
+    weights: Vec<T>,
+    /// Lowest output frequency in cycles per sample.
+    f_min: T,
+    /// Grid step, in octaves for a log grid and in cycles per sample for a linear one.
+    f_step: T,
+    frequency_scale: ScaleType,
+    gamma: T,
+}
+
+impl<T: CwtSample> SsqCwtExecutor<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    pub(crate) fn new(cwt: CommonCwtExecutor<T>, options: SsqOptions) -> Result<Self, ScaletError> {
+        check_sampling_frequency(options.sampling_frequency)?;
+        if !(0. ..f64::INFINITY).contains(&options.gamma) {
+            return Err(ScaletError::Generic(format!(
+                "SSQ threshold gamma must be finite and non-negative, but it was {}",
+                options.gamma
+            )));
+        }
+        let scales = cwt.view_scales();
+        if scales.is_empty() {
+            return Err(ScaletError::Generic(
+                "SSQ-CWT requires at least one scale".to_string(),
+            ));
+        }
+        // Scale a resonates at ω = w_peak / a radians per sample.
+        let (w_peak, _) = find_wavelet_peak(cwt.wavelet.clone(), &cwt.wavelet_search)?;
+        let to_cycles = |scale: T| w_peak / (T::TWO_PI * scale);
+        let mut f_min = T::INFINITY;
+        let mut f_max = T::zero();
+        for &scale in scales.iter() {
+            f_min = f_min.min(to_cycles(scale));
+            f_max = f_max.max(to_cycles(scale));
+        }
+        // Nothing above Nyquist is representable.
+        f_max = f_max.min(0.5f64.as_());
+        f_min = f_min.min(f_max);
+
+        let count = scales.len();
+        let mut ascending = match options.frequency_scale {
+            ScaleType::Log => geomspace(f_min, f_max, count)?,
+            ScaleType::Linear => linspace(f_min, f_max, count)?,
+        };
+        let f_step = if count > 1 {
+            match options.frequency_scale {
+                ScaleType::Log => (f_max / f_min).log2() / (count - 1).as_(),
+                ScaleType::Linear => (f_max - f_min) / (count - 1).as_(),
+            }
+        } else {
+            T::zero()
+        };
+
+        let fs: T = options.sampling_frequency.as_();
+        for v in ascending.iter_mut() {
+            *v *= fs;
+        }
+        ascending.reverse();
+
+        let mut weights = log_scale_weights(scales)?;
+        for (dst, &scale) in weights.iter_mut().zip(scales.iter()) {
+            *dst *= cwt.l1_amplitude(scale);
+        }
+
+        Ok(Self {
+            frequencies: ascending,
+            weights,
+            f_min,
+            f_step,
+            frequency_scale: options.frequency_scale,
+            gamma: options.gamma.as_(),
+            cwt,
+        })
+    }
+
+    /// Maps an instantaneous frequency in cycles per sample onto an output row.
+    #[inline]
+    fn frequency_row(&self, frequency: T) -> Option<usize> {
+        let count = self.frequencies.len();
+        if count == 1 || self.f_step <= T::zero() {
+            return Some(0);
+        }
+        let position = match self.frequency_scale {
+            ScaleType::Log => {
+                if frequency <= T::zero() {
+                    return None;
+                }
+                (frequency / self.f_min).log2() / self.f_step
+            }
+            ScaleType::Linear => (frequency - self.f_min) / self.f_step,
+        };
+        let bin = (position + 0.5f64.as_()).floor();
+        if bin < T::zero() || bin > (count - 1).as_() {
+            return None;
+        }
+        let bin: usize = bin.as_();
+        // Rows are stored high frequencies first.
+        Some(count - 1 - bin)
+    }
+}
+
+impl<T: CwtSample> SsqExecutor<T> for SsqCwtExecutor<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    fn execute(&self, input: &[T]) -> Result<SsqCwt<T>, ScaletError> {
+        let padded = self.cwt.pad(input)?;
+        let length = input.len();
+        let execution_length = self.cwt.execution_length;
+
+        let mut workspace = self.cwt.make_workspace()?;
+        let workspace = self.cwt.split_workspace(&mut workspace);
+        let scratch = workspace.scratch;
+        let padded_wx = workspace.padded_row;
+
+        let mut signal_fft = try_vec![Complex::<T>::zero(); self.cwt.real_spectrum_length()];
+        self.cwt.real_spectrum(&padded, &mut signal_fft, scratch)?;
+
+        let scales = self.cwt.view_scales();
+        let mut derivative_fft = try_vec![Complex::<T>::zero(); self.cwt.filter_length];
+        let mut padded_dwx = try_vec![Complex::<T>::zero(); execution_length];
+        let mut wx = try_vec![try_vec![Complex::<T>::zero(); length]; scales.len()];
+        let mut tx = try_vec![try_vec![Complex::<T>::zero(); length]; self.frequencies.len()];
+
+        let gamma_sqr = self.gamma * self.gamma;
+        let recip_two_pi = 1f64.as_() / T::TWO_PI;
+
+        for (index, ((&scale, &weight), wx_row)) in scales
+            .iter()
+            .zip(self.weights.iter())
+            .zip(wx.iter_mut())
+            .enumerate()
+        {
+            let wavelet_fft =
+                self.cwt
+                    .scale_filter(index, workspace.current_psi, workspace.filter)?;
+            self.cwt
+                .scale_coefficients(&signal_fft, wavelet_fft, scale, padded_wx, scratch)?;
+
+            // ∂ₜWx = IFFT(X · conj(ψ̂) · iω), the executor multiplies by the conjugate
+            // of the filter, so it is fed ψ̂ · (-iω).
+            for ((dst, &src), &w) in derivative_fft
+                .iter_mut()
+                .zip(wavelet_fft.iter())
+                .zip(self.cwt.psi.iter())
+            {
+                *dst = Complex::new(src.im * w, -src.re * w);
+            }
+            self.cwt.scale_coefficients(
+                &signal_fft,
+                &derivative_fft,
+                scale,
+                &mut padded_dwx,
+                scratch,
+            )?;
+
+            let wx_cropped = self.cwt.crop(padded_wx, length);
+            wx_row.copy_from_slice(wx_cropped);
+
+            for (b, (&w, &dw)) in wx_cropped
+                .iter()
+                .zip(self.cwt.crop(&padded_dwx, length).iter())
+                .enumerate()
+            {
+                let magnitude = w.norm_sqr();
+                if magnitude <= gamma_sqr {
+                    continue;
+                }
+                // Im(dw / w) = Im(dw · conj(w)) / |w|²
+                let phase_rate = fmla(dw.im, w.re, -dw.re * w.im) / magnitude;
+                let frequency = phase_rate * recip_two_pi;
+                if let Some(row) = self.frequency_row(frequency) {
+                    let dst = &mut tx[row][b];
+                    *dst = Complex::new(fmla(w.re, weight, dst.re), fmla(w.im, weight, dst.im));
+                }
+            }
+        }
+
+        Ok(SsqCwt {
+            wx,
+            tx,
+            frequencies: self.frequencies.clone(),
+        })
+    }
+
+    fn length(&self) -> usize {
+        self.cwt.length
+    }
+
+    fn filter_bank_size(&self) -> usize {
+        self.cwt.filter_bank_size()
+    }
+
+    fn view_scales(&self) -> &[T] {
+        self.cwt.view_scales()
+    }
+
+    fn view_frequencies(&self) -> &[T] {
+        &self.frequencies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MorletWavelet, PaddingMode, Scalet};
+    use std::f64::consts::PI;
+    use std::sync::Arc;
+
+    fn tone(n: usize, frequency: f64, fs: f64) -> Vec<f64> {
+        (0..n)
+            .map(|i| (2. * PI * frequency * i as f64 / fs).cos())
+            .collect()
+    }
+
+    fn ssq_options(frequency_scale: ScaleType) -> SsqOptions {
+        SsqOptions {
+            cwt: CwtOptions {
+                nv: 16,
+                ..Default::default()
+            },
+            frequency_scale,
+            sampling_frequency: 1000.,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tone_energy_lands_in_its_frequency_bin() -> Result<(), ScaletError> {
+        let n = 1024;
+        for frequency_scale in [ScaleType::Log, ScaleType::Linear] {
+            let options = ssq_options(frequency_scale);
+            let ssq = Scalet::make_ssq_f64(Arc::new(MorletWavelet::default()), n, options)?;
+            let result = ssq.execute(&tone(n, 62.5, options.sampling_frequency))?;
+            // Edge columns are affected by the boundary, the middle of the signal is not.
+            let energy = result
+                .tx
+                .iter()
+                .map(|row| {
+                    row[n / 4..3 * n / 4]
+                        .iter()
+                        .map(|x| x.norm_sqr())
+                        .sum::<f64>()
+                })
+                .collect::<Vec<f64>>();
+            let total = energy.iter().sum::<f64>();
+            let (peak, _) = energy
+                .iter()
+                .enumerate()
+                .fold((0, 0.), |acc, (i, &e)| if e > acc.1 { (i, e) } else { acc });
+            let frequencies = ssq.view_frequencies();
+            let step = (frequencies[peak.saturating_sub(1)] - frequencies[peak + 1]).abs();
+            assert!((frequencies[peak] - 62.5).abs() <= step);
+            // Nearly all of the energy is squeezed into the bins around the tone.
+            let near = energy[peak.saturating_sub(2)..peak + 3].iter().sum::<f64>();
+            assert!(near > 0.95 * total);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn wx_matches_cwt_execute() -> Result<(), ScaletError> {
+        let n = 700;
+        let signal = (0..n)
+            .map(|i| (0.07 * i as f64).sin() + 0.5 * (0.31 * i as f64).cos())
+            .collect::<Vec<f64>>();
+        for padding in [PaddingMode::None, PaddingMode::Reflect] {
+            let options = SsqOptions {
+                cwt: CwtOptions {
+                    padding,
+                    ..ssq_options(ScaleType::Log).cwt
+                },
+                ..ssq_options(ScaleType::Log)
+            };
+            let ssq = Scalet::make_ssq_f64(Arc::new(MorletWavelet::default()), n, options)?;
+            let cwt = Scalet::make_cwt_f64(Arc::new(MorletWavelet::default()), n, options.cwt)?;
+            assert_eq!(ssq.view_scales(), cwt.view_scales());
+            for length in [n, n - 123] {
+                let result = ssq.execute(&signal[..length])?;
+                assert_eq!(result.wx, cwt.execute(&signal[..length])?);
+                assert!(result.tx.iter().all(|row| row.len() == length));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn frequency_grid_follows_the_frequency_scale() -> Result<(), ScaletError> {
+        let n = 512;
+        for frequency_scale in [ScaleType::Log, ScaleType::Linear] {
+            let ssq = Scalet::make_ssq_f64(
+                Arc::new(MorletWavelet::default()),
+                n,
+                ssq_options(frequency_scale),
+            )?;
+            let frequencies = ssq.view_frequencies();
+            assert_eq!(frequencies.len(), ssq.view_scales().len());
+            assert!(frequencies.windows(2).all(|w| w[0] > w[1]));
+            assert!(frequencies[0] <= 500.);
+            let first = match frequency_scale {
+                ScaleType::Log => frequencies[0] / frequencies[1],
+                ScaleType::Linear => frequencies[0] - frequencies[1],
+            };
+            for w in frequencies.windows(2) {
+                let step = match frequency_scale {
+                    ScaleType::Log => w[0] / w[1],
+                    ScaleType::Linear => w[0] - w[1],
+                };
+                assert!((step - first).abs() < 1e-9 * first);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn gamma_is_an_absolute_threshold() -> Result<(), ScaletError> {
+        let n = 512;
+        let signal = tone(n, 40., 1000.);
+        let options = ssq_options(ScaleType::Log);
+        let ssq = Scalet::make_ssq_f64(Arc::new(MorletWavelet::default()), n, options)?;
+        let result = ssq.execute(&signal)?;
+        let peak = result
+            .wx
+            .iter()
+            .flatten()
+            .fold(0f64, |acc, x| acc.max(x.norm_sqr().sqrt()));
+        assert!(result.tx.iter().flatten().any(|x| x.norm_sqr() > 0.));
+
+        // Above the largest coefficient nothing is reassigned.
+        let silent = Scalet::make_ssq_f64(
+            Arc::new(MorletWavelet::default()),
+            n,
+            SsqOptions {
+                gamma: peak * 1.01,
+                ..options
+            },
+        )?
+        .execute(&signal)?;
+        assert!(silent.tx.iter().flatten().all(|x| x.norm_sqr() == 0.));
+
+        // The threshold does not scale with the signal: a quieter copy of the tone falls
+        // below a gamma the original clears.
+        let gamma = peak * 0.5;
+        let thresholded = Scalet::make_ssq_f64(
+            Arc::new(MorletWavelet::default()),
+            n,
+            SsqOptions { gamma, ..options },
+        )?;
+        let loud = thresholded.execute(&signal)?;
+        assert!(loud.tx.iter().flatten().any(|x| x.norm_sqr() > 0.));
+        let quiet = signal.iter().map(|x| x * 0.25).collect::<Vec<f64>>();
+        let quiet = thresholded.execute(&quiet)?;
+        assert!(quiet.tx.iter().flatten().all(|x| x.norm_sqr() == 0.));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_options_are_rejected() {
+        let n = 256;
+        for sampling_frequency in [0., -1000., f64::NAN, f64::INFINITY] {
+            let options = SsqOptions {
+                sampling_frequency,
+                ..ssq_options(ScaleType::Log)
+            };
+            assert!(
+                Scalet::make_ssq_f64(Arc::new(MorletWavelet::default()), n, options).is_err(),
+                "sampling frequency {sampling_frequency}"
+            );
+        }
+        for gamma in [-1e-6, f64::NAN, f64::INFINITY] {
+            let options = SsqOptions {
+                gamma,
+                ..ssq_options(ScaleType::Log)
+            };
+            assert!(
+                Scalet::make_ssq_f64(Arc::new(MorletWavelet::default()), n, options).is_err(),
+                "gamma {gamma}"
+            );
+        }
+        let options = SsqOptions {
+            gamma: 0.,
+            ..ssq_options(ScaleType::Log)
+        };
+        assert!(Scalet::make_ssq_f64(Arc::new(MorletWavelet::default()), n, options).is_ok());
+    }
+}
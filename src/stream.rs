@@ -0,0 +1,356 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::cwt_executor::CommonCwtExecutor;
+use crate::err::try_vec;
+use crate::sample::CwtSample;
+use crate::{CwtExecutor, CwtOptions, ScaletError};
+use num_complex::Complex;
+use num_traits::{AsPrimitive, Zero};
+
+/// Configuration of a streaming CWT.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CwtStreamOptions {
+    /// Options of the underlying transform.
+    ///
+    /// [`CwtOptions::padding`] is not used, the stream starts and ends with zeros.
+    /// [`CwtOptions::thread_count`] is not used either, blocks run on the calling thread.
+    pub cwt: CwtOptions,
+    /// Minimum number of columns finalized per block.
+    ///
+    /// Larger hops amortize the FFTs better at the cost of latency, the value may be
+    /// rounded up to fill an FFT-friendly block. Default 1024.
+    pub hop: usize,
+    /// Relative magnitude below which the tails of the largest-scale wavelet are neglected,
+    /// it sets how much context every block carries. Default 1e-5.
+    pub tolerance: f64,
+    /// Signal length the scales are planned for, as if the stream were an offline
+    /// transform of that many samples.
+    ///
+    /// Scale bounds are derived from it with [`CwtOptions::scale_cutoffs`], the same way as
+    /// for an offline executor of that length. The largest scale, and with it the lowest
+    /// analyzed frequency, grows with this length, as does the context every block carries
+    /// and so the latency. Default 4096.
+    pub planning_length: usize,
+}
+
+impl Default for CwtStreamOptions {
+    fn default() -> Self {
+        Self {
+            cwt: CwtOptions::default(),
+            hop: 1024,
+            tolerance: 1e-5,
+            planning_length: 4096,
+        }
+    }
+}
+
+/// Block-wise Continuous Wavelet Transform of an unbounded signal.
+///
+/// Samples are pushed in chunks of any size and the scalogram comes out as finalized columns,
+/// using overlap-save: every block carries enough past and future context for the largest
+/// scale that its central columns match the offline transform within the configured tolerance.
+///
+/// The stream treats the samples before its start and after its end as zeros, so it is
+/// equivalent to an offline transform over the same scales of the signal extended with at
+/// least [`context`](Self::context) zeros on both sides, cropped back to the signal.
+/// [`PaddingMode::Zero`](crate::PaddingMode::Zero) comes close but pads less than the support
+/// of the largest scales. The remaining difference comes from the neglected wavelet tails and
+/// from the smallest scales, whose response is cut at Nyquist and so depends on the FFT length.
+/// With the default options it is in the order of `1e-4` of the peak magnitude.
+pub trait CwtStreamExecutor<T> {
+    /// Appends samples to the stream.
+    ///
+    /// # Returns
+    /// The newly finalized columns as `[num_scales, columns]`, the number of columns is a
+    /// multiple of [`hop`](Self::hop) and may be zero.
+    fn push(&mut self, samples: &[T]) -> Result<Vec<Vec<Complex<T>>>, ScaletError>;
+    /// Finalizes all pending columns, treating the samples after the end as zeros,
+    /// and resets the stream.
+    fn flush(&mut self) -> Result<Vec<Vec<Complex<T>>>, ScaletError>;
+    /// Drops all buffered samples and starts a new stream.
+    fn reset(&mut self);
+    /// Worst-case number of samples pushed after a sample before its column is finalized.
+    ///
+    /// Columns are finalized a whole block at a time, once [`hop`](Self::hop) columns and
+    /// their [`context`](Self::context) of future samples are buffered, so the first column
+    /// of a block waits for `hop + context - 1` more samples and the last one for `context`.
+    fn latency(&self) -> usize;
+    /// Number of samples of past and future context every block carries.
+    fn context(&self) -> usize;
+    /// Number of columns finalized per block.
+    fn hop(&self) -> usize;
+    /// Provides a zero-copy view of the CWT scales.
+    fn view_scales(&self) -> &[T];
+}
+
+pub(crate) struct CwtStream<T> {
+    /// Executor running whole blocks with a circular convolution.
+    cwt: CommonCwtExecutor<T>,
+    /// Samples of context kept on both sides of the finalized columns.
+    context: usize,
+    hop: usize,
+    /// Pending samples, starting with `context` samples of past context.
+    buffer: Vec<T>,
+    /// Flat `[num_scales, block_length]` coefficients of the last block.
+    block: Vec<Complex<T>>,
+    scratch: Vec<Complex<T>>,
+}
+
+impl<T: CwtSample> CwtStream<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    pub(crate) fn new(cwt: CommonCwtExecutor<T>, context: usize) -> Result<Self, ScaletError> {
+        let block_length = cwt.execution_length;
+        let mut buffer = try_vec![T::zero(); context];
+        buffer.reserve(block_length);
+        let block = try_vec![Complex::zero(); cwt.scales.len() * block_length];
+        let scratch = try_vec![Complex::zero(); cwt.scratch_length()];
+        Ok(Self {
+            hop: block_length - 2 * context,
+            cwt,
+            context,
+            buffer,
+            block,
+            scratch,
+        })
+    }
+
+    fn empty_columns(&self) -> Result<Vec<Vec<Complex<T>>>, ScaletError> {
+        Ok(try_vec![Vec::new(); self.cwt.scales.len()])
+    }
+
+    /// Transforms every complete block in the buffer, appending the central columns to `dst`.
+    fn drain_blocks(&mut self, dst: &mut [Vec<Complex<T>>]) -> Result<(), ScaletError> {
+        let block_length = self.cwt.execution_length;
+        while self.buffer.len() >= block_length {
+            self.cwt.execute_into(
+                &self.buffer[..block_length],
+                &mut self.block,
+                &mut self.scratch,
+            )?;
+            for (dst, row) in dst.iter_mut().zip(self.block.chunks_exact(block_length)) {
+                dst.extend_from_slice(&row[self.context..self.context + self.hop]);
+            }
+            self.buffer.drain(..self.hop);
+        }
+        Ok(())
+    }
+}
+
+impl<T: CwtSample> CwtStreamExecutor<T> for CwtStream<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    fn push(&mut self, samples: &[T]) -> Result<Vec<Vec<Complex<T>>>, ScaletError> {
+        let mut result = self.empty_columns()?;
+        let mut samples = samples;
+        while !samples.is_empty() {
+            // Feed at most one block at a time to keep the buffer bounded.
+            let take = (self.cwt.execution_length - self.buffer.len()).min(samples.len());
+            self.buffer.extend_from_slice(&samples[..take]);
+            samples = &samples[take..];
+            self.drain_blocks(&mut result)?;
+        }
+        Ok(result)
+    }
+
+    fn flush(&mut self) -> Result<Vec<Vec<Complex<T>>>, ScaletError> {
+        let mut result = self.empty_columns()?;
+        let pending = self.buffer.len() - self.context;
+        if pending > 0 {
+            // Future context is zeros, complete the last block and drop the excess columns.
+            let blocks = pending.div_ceil(self.hop);
+            self.buffer
+                .resize(blocks * self.hop + 2 * self.context, T::zero());
+            self.drain_blocks(&mut result)?;
+            for row in result.iter_mut() {
+                row.truncate(pending);
+            }
+        }
+        self.reset();
+        Ok(result)
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.buffer.resize(self.context, T::zero());
+    }
+
+    fn latency(&self) -> usize {
+        self.hop + self.context - 1
+    }
+
+    fn context(&self) -> usize {
+        self.context
+    }
+
+    fn hop(&self) -> usize {
+        self.hop
+    }
+
+    fn view_scales(&self) -> &[T] {
+        self.cwt.view_scales()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MorletWavelet, Scalet};
+    use std::sync::Arc;
+
+    fn stream_options() -> CwtStreamOptions {
+        CwtStreamOptions {
+            cwt: CwtOptions {
+                nv: 8,
+                ..Default::default()
+            },
+            hop: 128,
+            ..Default::default()
+        }
+    }
+
+    fn signal(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64;
+                (0.05 * t).sin() + 0.3 * (0.4 * t + 0.0002 * t * t).cos()
+            })
+            .collect()
+    }
+
+    /// Pushes `input` in chunks of `chunk` samples and flushes, collecting every column.
+    fn run_stream(
+        stream: &mut (dyn CwtStreamExecutor<f64> + Send + Sync),
+        input: &[f64],
+        chunk: usize,
+    ) -> Result<Vec<Vec<Complex<f64>>>, ScaletError> {
+        let mut rows = vec![Vec::new(); stream.view_scales().len()];
+        for samples in input.chunks(chunk) {
+            let columns = stream.push(samples)?;
+            for (row, column) in rows.iter_mut().zip(columns) {
+                assert_eq!(column.len() % stream.hop(), 0);
+                row.extend(column);
+            }
+        }
+        for (row, column) in rows.iter_mut().zip(stream.flush()?) {
+            row.extend(column);
+        }
+        Ok(rows)
+    }
+
+    #[test]
+    fn stream_matches_zero_extended_offline_transform() -> Result<(), ScaletError> {
+        let n = 1000;
+        let input = signal(n);
+        let mut stream =
+            Scalet::make_cwt_stream_f64(Arc::new(MorletWavelet::default()), stream_options())?;
+        let rows = run_stream(stream.as_mut(), &input, 97)?;
+
+        let context = stream.context();
+        let mut extended = vec![0.; n + 2 * context];
+        extended[context..context + n].copy_from_slice(&input);
+        let offline = Scalet::make_cwt_with_scales_f64(
+            Arc::new(MorletWavelet::default()),
+            extended.len(),
+            stream.view_scales(),
+            stream_options().cwt,
+        )?
+        .execute(&extended)?;
+
+        let mut peak = 0f64;
+        let mut difference = 0f64;
+        for (row, expected) in rows.iter().zip(offline.iter()) {
+            assert_eq!(row.len(), n);
+            for (a, b) in row.iter().zip(expected[context..context + n].iter()) {
+                peak = peak.max(b.norm_sqr().sqrt());
+                difference = difference.max((a - b).norm_sqr().sqrt());
+            }
+        }
+        assert!(difference < 1e-3 * peak);
+        Ok(())
+    }
+
+    #[test]
+    fn stream_is_independent_of_chunking() -> Result<(), ScaletError> {
+        let input = signal(900);
+        let mut stream =
+            Scalet::make_cwt_stream_f64(Arc::new(MorletWavelet::default()), stream_options())?;
+        let whole = run_stream(stream.as_mut(), &input, input.len())?;
+        // Flushing resets the stream, so it can be reused right away.
+        assert_eq!(run_stream(stream.as_mut(), &input, 1)?, whole);
+        assert_eq!(run_stream(stream.as_mut(), &input, 301)?, whole);
+        stream.push(&input[..500])?;
+        stream.reset();
+        assert_eq!(run_stream(stream.as_mut(), &input, 64)?, whole);
+        Ok(())
+    }
+
+    #[test]
+    fn latency_is_the_wait_of_the_first_column_of_a_block() -> Result<(), ScaletError> {
+        let mut stream =
+            Scalet::make_cwt_stream_f64(Arc::new(MorletWavelet::default()), stream_options())?;
+        let hop = stream.hop();
+        assert_eq!(stream.latency(), hop + stream.context() - 1);
+        let input = signal(stream.latency() + 2 * hop);
+        // Column 0 is finalized only once `latency` samples followed it.
+        let columns = stream.push(&input[..stream.latency()])?;
+        assert!(columns.iter().all(|row| row.is_empty()));
+        let columns = stream.push(&input[stream.latency()..stream.latency() + 1])?;
+        assert!(columns.iter().all(|row| row.len() == hop));
+        // The next block follows `hop` samples later.
+        let next = stream.latency() + 1 + hop;
+        let columns = stream.push(&input[stream.latency() + 1..next - 1])?;
+        assert!(columns.iter().all(|row| row.is_empty()));
+        let columns = stream.push(&input[next - 1..next])?;
+        assert!(columns.iter().all(|row| row.len() == hop));
+        Ok(())
+    }
+
+    #[test]
+    fn stream_rejects_empty_planning() {
+        for options in [
+            CwtStreamOptions {
+                hop: 0,
+                ..stream_options()
+            },
+            CwtStreamOptions {
+                planning_length: 0,
+                ..stream_options()
+            },
+        ] {
+            assert!(
+                Scalet::make_cwt_stream_f64(Arc::new(MorletWavelet::default()), options).is_err()
+            );
+        }
+    }
+}
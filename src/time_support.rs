@@ -0,0 +1,177 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::err::try_vec;
+use crate::factory::gen_psi;
+use crate::padding::fast_fft_length;
+use crate::sample::CwtSample;
+use crate::{CwtWavelet, ScaletError};
+use num_complex::Complex;
+use num_traits::{AsPrimitive, Zero};
+use zaft::FftDirection;
+
+/// Largest FFT length tried while searching for the wavelet support.
+const MAX_SUPPORT_GRID: usize = 1 << 26;
+
+/// Samples of a wavelet dilated to `scale`, in the time domain.
+///
+/// The grid has `length` samples, with `t = 0` at index zero and negative times wrapped
+/// to the end, as produced by the inverse FFT of the frequency-domain filter.
+pub(crate) fn time_domain_wavelet<T: CwtSample>(
+    wavelet: &(dyn CwtWavelet<T> + Send + Sync),
+    scale: T,
+    length: usize,
+) -> Result<Vec<Complex<T>>, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    let mut omegas = gen_psi::<T>(length)?;
+    for w in omegas.iter_mut() {
+        *w *= scale;
+    }
+    let mut samples = wavelet.make_wavelet(&omegas)?;
+    if samples.len() != length {
+        return Err(ScaletError::WaveletInvalidSize(length, samples.len()));
+    }
+    let fft = T::make_fft(length, FftDirection::Inverse)?;
+    let mut scratch = try_vec![Complex::zero(); fft.scratch_length()];
+    fft.execute_with_scratch(&mut samples, &mut scratch)
+        .map_err(|x| ScaletError::FftError(x.to_string()))?;
+    Ok(samples)
+}
+
+/// Half-width, in samples, outside of which the wavelet dilated to `scale` stays below
+/// `tolerance` of its peak magnitude.
+///
+/// The grid is doubled until the wavelet fits comfortably, so the circular inverse FFT
+/// does not fold the tails back.
+pub(crate) fn wavelet_half_support<T: CwtSample>(
+    wavelet: &(dyn CwtWavelet<T> + Send + Sync),
+    scale: T,
+    tolerance: T,
+) -> Result<usize, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    if !(tolerance > T::zero() && tolerance < 1f64.as_()) {
+        return Err(ScaletError::Generic(format!(
+            "Wavelet support tolerance must be in (0, 1), but it was {tolerance}"
+        )));
+    }
+    let start: usize = (scale * 16f64.as_()).ceil().as_();
+    let mut length = fast_fft_length(start.max(64));
+    while length <= MAX_SUPPORT_GRID {
+        let samples = time_domain_wavelet(wavelet, scale, length)?;
+        let peak = samples
+            .iter()
+            .fold(T::zero(), |acc, x| acc.max(x.norm_sqr()));
+        if peak <= T::zero() {
+            return Err(ScaletError::Generic(
+                "Wavelet has no energy at the requested scale".to_string(),
+            ));
+        }
+        let threshold = peak * tolerance * tolerance;
+        let half_support = samples
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| x.norm_sqr() > threshold)
+            .map(|(i, _)| i.min(length - i))
+            .max()
+            .unwrap_or(0);
+        if half_support < length / 4 {
+            return Ok(half_support + 1);
+        }
+        length = fast_fft_length(length * 2);
+    }
+    Err(ScaletError::Generic(format!(
+        "Wavelet support at scale {scale} exceeds {MAX_SUPPORT_GRID} samples"
+    )))
+}
+
+/// Scale the e-folding time is measured at, large enough for the sampling grid
+/// to resolve the wavelet envelope.
+const E_FOLDING_REFERENCE_SCALE: f64 = 64.;
+
+/// E-folding time of the wavelet per unit scale, in samples.
+///
+/// Following Torrence & Compo, the e-folding time is the distance from the wavelet center
+/// at which its magnitude drops to `e⁻¹` of the peak, so that the wavelet power of an edge
+/// discontinuity falls by `e⁻²`. The time spread of a wavelet scales linearly with dilation,
+/// the e-folding time at scale `a` is `a` times the returned ratio (`√2` for the Morlet wavelet).
+pub(crate) fn e_folding_ratio<T: CwtSample>(
+    wavelet: &(dyn CwtWavelet<T> + Send + Sync),
+) -> Result<T, ScaletError>
+where
+    usize: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    let scale: T = E_FOLDING_REFERENCE_SCALE.as_();
+    let tolerance: T = (-1f64).exp().as_();
+    // Grid wide enough for the e-folding point to sit far from the wrap-around.
+    let half_support = wavelet_half_support(wavelet, scale, tolerance)?;
+    let length = fast_fft_length((half_support * 8).max(1024));
+    let samples = time_domain_wavelet(wavelet, scale, length)?;
+    let magnitudes = samples
+        .iter()
+        .map(|x| x.norm_sqr().sqrt())
+        .collect::<Vec<T>>();
+
+    let (center, peak) = magnitudes
+        .iter()
+        .enumerate()
+        .fold(
+            (0usize, T::zero()),
+            |acc, (i, &v)| if v > acc.1 { (i, v) } else { acc },
+        );
+    if peak <= T::zero() {
+        return Err(ScaletError::Generic(
+            "Wavelet has no energy at the requested scale".to_string(),
+        ));
+    }
+    let threshold = peak * tolerance;
+    // Envelope at distance k from the center, the wider of both sides.
+    let envelope = |k: usize| {
+        magnitudes[(center + k) % length].max(magnitudes[(center + length - k % length) % length])
+    };
+    let last = (1..length / 2)
+        .rev()
+        .find(|&k| envelope(k) > threshold)
+        .unwrap_or(0);
+    // Linear interpolation of the crossing between the last sample above the threshold
+    // and the next one.
+    let above = envelope(last);
+    let below = envelope(last + 1);
+    let fraction = if above > below {
+        (above - threshold) / (above - below)
+    } else {
+        T::zero()
+    };
+    Ok((last.as_() + fraction) / scale)
+}
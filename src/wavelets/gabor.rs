@@ -26,6 +26,7 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::cwt_filter::ANALYTIC_LEAKAGE;
 use crate::err::try_vec;
 use crate::sample::CwtSample;
 use crate::{CwtWavelet, ScaletError};
@@ -92,6 +93,14 @@ where
 {
     fn make_wavelet(&self, omegas: &[T]) -> Result<Vec<Complex<T>>, ScaletError> {
         let mut out = try_vec![Complex::<T>::zero(); omegas.len()];
+        self.make_wavelet_into(omegas, &mut out)?;
+        Ok(out)
+    }
+
+    fn make_wavelet_into(&self, omegas: &[T], out: &mut [Complex<T>]) -> Result<(), ScaletError> {
+        if omegas.len() != out.len() {
+            return Err(ScaletError::WaveletInvalidSize(omegas.len(), out.len()));
+        }
 
         if self.mx0.partial_cmp(&T::zero()).unwrap_or(Ordering::Equal) == Ordering::Equal {
             for (dst, &w) in out.iter_mut().zip(omegas.iter()) {
@@ -112,6 +121,12 @@ where
             }
         }
 
-        Ok(out)
+        Ok(())
+    }
+
+    /// The envelope on the negative axis is bounded by `exp(-α²μ²)` relative to the peak `α`,
+    /// so every `α·μ` above roughly 3.4 counts as analytic.
+    fn is_analytic(&self) -> bool {
+        self.mu > T::zero() && (-self.alpha_s2 * self.mu * self.mu).exp() <= ANALYTIC_LEAKAGE.as_()
     }
 }
@@ -76,6 +76,14 @@ where
 {
     fn make_wavelet(&self, omegas: &[T]) -> Result<Vec<Complex<T>>, ScaletError> {
         let mut out = try_vec![Complex::<T>::zero(); omegas.len()];
+        self.make_wavelet_into(omegas, &mut out)?;
+        Ok(out)
+    }
+
+    fn make_wavelet_into(&self, omegas: &[T], out: &mut [Complex<T>]) -> Result<(), ScaletError> {
+        if omegas.len() != out.len() {
+            return Err(ScaletError::WaveletInvalidSize(omegas.len(), out.len()));
+        }
 
         let c1 = T::TWO_OVER_5_SQ_PI_POWER_M0_25;
         let c0 = -0.5f64.as_();
@@ -92,6 +100,11 @@ where
             *dst = Complex::new(a, T::zero());
         }
 
-        Ok(out)
+        Ok(())
+    }
+
+    fn is_analytic(&self) -> bool {
+        // The response is nullified below `mu`.
+        self.mu >= T::zero()
     }
 }
@@ -26,6 +26,7 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::cwt_filter::ANALYTIC_LEAKAGE;
 use crate::err::try_vec;
 use crate::mla::fmla;
 use crate::sample::CwtSample;
@@ -93,6 +94,14 @@ where
 {
     fn make_wavelet(&self, omegas: &[T]) -> Result<Vec<Complex<T>>, ScaletError> {
         let mut out = try_vec![Complex::<T>::zero(); omegas.len()];
+        self.make_wavelet_into(omegas, &mut out)?;
+        Ok(out)
+    }
+
+    fn make_wavelet_into(&self, omegas: &[T], out: &mut [Complex<T>]) -> Result<(), ScaletError> {
+        if omegas.len() != out.len() {
+            return Err(ScaletError::WaveletInvalidSize(omegas.len(), out.len()));
+        }
 
         let c0 = -0.5f64.as_();
 
@@ -102,6 +111,12 @@ where
             *dst = Complex::new(a, T::zero());
         }
 
-        Ok(out)
+        Ok(())
+    }
+
+    /// Both terms on the negative axis are bounded by `exp(-mu²/2)` relative to the peak,
+    /// `1.5e-8` for `mu = 6`, so every `mu` above roughly 4.8 counts as analytic.
+    fn is_analytic(&self) -> bool {
+        (-0.5f64.as_() * self.mu * self.mu).exp() <= ANALYTIC_LEAKAGE.as_()
     }
 }
@@ -0,0 +1,225 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 12/2025. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::cwt_executor::{CommonCwtExecutor, ScaleWorkspace};
+use crate::err::try_vec;
+use crate::freqs::scale_to_frequencies_impl;
+use crate::padding::pad_signal;
+use crate::sample::CwtSample;
+use crate::{FrequencyMode, ScaletError};
+use num_complex::Complex;
+use num_traits::{AsPrimitive, Zero};
+
+/// Cross-wavelet transform `W_xy = W_x · conj(W_y)` of two signals.
+///
+/// Every matrix is laid out as `[scale][time]`, like the output of
+/// [`CwtExecutor::execute`](crate::CwtExecutor::execute).
+#[derive(Debug, Clone)]
+pub struct CrossWavelet<T> {
+    /// Cross-wavelet coefficients `W_xy`.
+    pub coeffs: Vec<Vec<Complex<T>>>,
+    /// Cross-wavelet power `|W_xy|`.
+    pub power: Vec<Vec<T>>,
+    /// Phase difference `arg(W_xy)` in radians, in `[-π, π]`.
+    ///
+    /// Positive values mean `x` leads `y`.
+    pub phase: Vec<Vec<T>>,
+    /// Time lag implied by the phase difference at the center frequency of each scale,
+    /// in samples, positive when `y` lags behind `x`.
+    ///
+    /// The lag is only defined modulo one period of the scale.
+    pub lag: Vec<Vec<T>>,
+}
+
+impl<T: CwtSample> CommonCwtExecutor<T>
+where
+    f64: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    /// Validates a pair of real signals and returns their spectra,
+    /// one forward FFT per signal.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn pair_spectra(
+        &self,
+        x: &[T],
+        y: &[T],
+    ) -> Result<(Vec<Complex<T>>, Vec<Complex<T>>), ScaletError> {
+        self.check_length(x.len())?;
+        if x.len() != y.len() {
+            return Err(ScaletError::InvalidInputSize(x.len(), y.len()));
+        }
+        let bins = self.real_spectrum_length();
+        let mut scratch = try_vec![Complex::zero(); self.scratch_length];
+        let mut padded = try_vec![T::zero(); self.execution_length];
+        let mut x_fft = try_vec![Complex::zero(); bins];
+        let mut y_fft = try_vec![Complex::zero(); bins];
+        for (signal, spectrum) in [(x, &mut x_fft), (y, &mut y_fft)] {
+            pad_signal(signal, &mut padded, self.pad_left(x.len()), self.padding);
+            self.real_spectrum(&padded, spectrum, &mut scratch)?;
+        }
+        Ok((x_fft, y_fft))
+    }
+
+    /// Computes the coefficients of both signals at the scale at `index` with a single filter,
+    /// `W_x` lands in `dst` and `W_y` in the workspace row, cropped by the returned view.
+    pub(crate) fn pair_scale_coefficients<'a>(
+        &self,
+        index: usize,
+        x_fft: &[Complex<T>],
+        y_fft: &[Complex<T>],
+        workspace: &'a mut ScaleWorkspace<'_, T>,
+        dst: &mut [Complex<T>],
+    ) -> Result<&'a [Complex<T>], ScaletError> {
+        let scale = self.scales[index];
+        let wavelet_fft = self.scale_filter(index, workspace.current_psi, workspace.filter)?;
+        self.scale_coefficients(
+            x_fft,
+            wavelet_fft,
+            scale,
+            workspace.padded_row,
+            workspace.scratch,
+        )?;
+        dst.copy_from_slice(self.crop(workspace.padded_row, dst.len()));
+        self.scale_coefficients(
+            y_fft,
+            wavelet_fft,
+            scale,
+            workspace.padded_row,
+            workspace.scratch,
+        )?;
+        Ok(self.crop(workspace.padded_row, dst.len()))
+    }
+
+    pub(crate) fn cross_wavelet_impl(
+        &self,
+        x: &[T],
+        y: &[T],
+    ) -> Result<CrossWavelet<T>, ScaletError> {
+        // One forward FFT per signal, the scale loop below shares each filter between both.
+        let (x_fft, y_fft) = self.pair_spectra(x, y)?;
+        let length = x.len();
+
+        let coeffs = self.execute_rows(length, |index, workspace, dst| {
+            let w_y = self.pair_scale_coefficients(index, &x_fft, &y_fft, workspace, dst)?;
+            for (dst, &w_y) in dst.iter_mut().zip(w_y.iter()) {
+                *dst = *dst * w_y.conj();
+            }
+            Ok(())
+        })?;
+
+        // Cycles per sample of every scale, the lag is the phase over the angular frequency.
+        let frequencies = scale_to_frequencies_impl(
+            self.wavelet.clone(),
+            &self.scales,
+            1f64.as_(),
+            self.execution_length,
+            FrequencyMode::Center,
+            &self.wavelet_search,
+        )?;
+
+        let mut power = try_vec![try_vec![T::zero(); length]; coeffs.len()];
+        let mut phase = try_vec![try_vec![T::zero(); length]; coeffs.len()];
+        let mut lag = try_vec![try_vec![T::zero(); length]; coeffs.len()];
+        for (index, row) in coeffs.iter().enumerate() {
+            self.spectrum_arithmetic.magnitude(&mut power[index], row);
+            let omega = T::TWO_PI * frequencies[index];
+            for ((phase, lag), w) in phase[index]
+                .iter_mut()
+                .zip(lag[index].iter_mut())
+                .zip(row.iter())
+            {
+                *phase = w.im.atan2(w.re);
+                *lag = *phase / omega;
+            }
+        }
+
+        Ok(CrossWavelet {
+            coeffs,
+            power,
+            phase,
+            lag,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CwtOptions, CwtWavelet, FrequencyMode, MorletWavelet, Scalet, ScaletError, WaveletSearch,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn cross_wavelet_recovers_a_delay() -> Result<(), ScaletError> {
+        let n = 1024;
+        let frequency = 0.04;
+        let delay = 3.;
+        let omega = std::f64::consts::TAU * frequency;
+        let x = (0..n)
+            .map(|i| (omega * i as f64).cos())
+            .collect::<Vec<f64>>();
+        // `y` lags `x` by `delay` samples.
+        let y = (0..n)
+            .map(|i| (omega * (i as f64 - delay)).cos())
+            .collect::<Vec<f64>>();
+        let wavelet: Arc<dyn CwtWavelet<f64> + Send + Sync> = Arc::new(MorletWavelet::default());
+        let executor = Scalet::make_cwt_f64(wavelet.clone(), n, CwtOptions::default())?;
+        let cross = executor.cross_wavelet(&x, &y)?;
+
+        let w_x = executor.execute(&x)?;
+        let w_y = executor.execute(&y)?;
+        for (index, row) in cross.coeffs.iter().enumerate() {
+            for (t, w) in row.iter().enumerate() {
+                let expected = w_x[index][t] * w_y[index][t].conj();
+                assert!((w - expected).norm() <= 1e-9 * (1. + expected.norm()));
+                assert!((cross.power[index][t] - w.norm()).abs() <= 1e-9 * (1. + w.norm()));
+            }
+        }
+
+        // The scale centered closest to the tone sees the delay in its phase and lag.
+        let frequencies = Scalet::scales_to_frequencies_with_mode_f64(
+            wavelet,
+            executor.view_scales(),
+            n,
+            1.,
+            FrequencyMode::Center,
+            &WaveletSearch::default(),
+        )?;
+        let index = frequencies
+            .iter()
+            .enumerate()
+            .min_by(|a, b| (a.1 - frequency).abs().total_cmp(&(b.1 - frequency).abs()))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        for t in n / 4..3 * n / 4 {
+            assert!((cross.phase[index][t] - omega * delay).abs() < 1e-6);
+            assert!((cross.lag[index][t] - delay).abs() < 0.05 * delay);
+        }
+        Ok(())
+    }
+}